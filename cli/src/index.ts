@@ -6,7 +6,6 @@ import * as anchor from "@coral-xyz/anchor";
 import * as fs from "fs";
 import {
   RecurringPaymentsSDK,
-  type PolicyType,
   type PaymentFrequency,
   createMemoBuffer,
 } from "@tributary-so/sdk";
@@ -107,8 +106,8 @@ program
   .requiredOption("-n, --name <string>", "Gateway name")
   .requiredOption("-u, --url <string>", "Gateway URL")
   .option(
-    "--admin-keypath <path>",
-    "Path to admin keypair file (defaults to main keypath)"
+    "--approver-keypath <path>",
+    "Path to the gateway approver keypair file (defaults to main keypath)"
   )
   .action(async (options) => {
     try {
@@ -119,14 +118,14 @@ program
       const name = options.name;
       const url = options.url;
 
-      let adminKeypair = readKeypairFromFile(program.opts().keypath);
-      if (options.adminKeypath) {
-        adminKeypair = readKeypairFromFile(options.adminKeypath);
+      let approverKeypair = readKeypairFromFile(program.opts().keypath);
+      if (options.approverKeypath) {
+        approverKeypair = readKeypairFromFile(options.approverKeypath);
       }
 
       const sdk = new RecurringPaymentsSDK(
         connection,
-        new anchor.Wallet(adminKeypair)
+        new anchor.Wallet(approverKeypair)
       );
 
       const instruction = await sdk.createPaymentGateway(
@@ -137,7 +136,7 @@ program
         url
       );
       const tx = new anchor.web3.Transaction().add(instruction);
-      const signature = await connection.sendTransaction(tx, [adminKeypair]);
+      const signature = await connection.sendTransaction(tx, [approverKeypair]);
 
       console.log("Payment gateway created successfully!");
       console.log("Transaction signature:", signature);
@@ -152,6 +151,10 @@ program
   .command("delete-gateway")
   .description("Delete a payment gateway")
   .requiredOption("-a, --authority <pubkey>", "Gateway authority public key")
+  .option(
+    "--successor-keypath <path>",
+    "Keypair of the gateway authority taking over the remaining policies"
+  )
   .action(async (options) => {
     try {
       const sdk = createSDK(
@@ -159,10 +162,19 @@ program
         program.opts().keypath
       );
       const authority = new PublicKey(options.authority);
+      const successor = options.successorKeypath
+        ? readKeypairFromFile(options.successorKeypath)
+        : undefined;
 
-      const instruction = await sdk.deletePaymentGateway(authority);
+      const instruction = await sdk.deletePaymentGateway(
+        authority,
+        successor?.publicKey
+      );
       const tx = new anchor.web3.Transaction().add(instruction);
-      const signature = await sdk.provider.sendAndConfirm(tx);
+      const signature = await sdk.provider.sendAndConfirm(
+        tx,
+        successor ? [successor] : []
+      );
 
       console.log("Payment gateway deleted successfully!");
       console.log("Transaction signature:", signature);
@@ -192,6 +204,11 @@ program
     "Payment frequency (daily|weekly|monthly|quarterly|semiAnnually|annually)",
     "daily"
   )
+  .option(
+    "--hold-seconds <number>",
+    "Seconds each payment is held in escrow for disputes",
+    "0"
+  )
   .action(async (options) => {
     try {
       const sdk = createSDK(
@@ -202,19 +219,6 @@ program
       const recipient = new PublicKey(options.recipient);
       const gateway = new PublicKey(options.gateway);
 
-      // Create policy type
-      // FIXME:
-      const policyType: PolicyType = {
-        subscription: {
-          amount: new anchor.BN(options.amount),
-          autoRenew: options.autoRenew,
-          maxRenewals: options.maxRenewals
-            ? parseInt(options.maxRenewals)
-            : null,
-          padding: Array(8).fill(new anchor.BN(0)),
-        },
-      };
-
       // Create payment frequency
       const paymentFrequency: PaymentFrequency = {
         [options.frequency]: {},
@@ -223,14 +227,17 @@ program
       // Create memo
       const memo = createMemoBuffer(options.memo);
 
-      // FIXME:
       const instruction = await sdk.createPaymentPolicy(
         tokenMint,
         recipient,
         gateway,
-        policyType,
+        new anchor.BN(options.amount),
+        options.autoRenew,
+        options.maxRenewals ? parseInt(options.maxRenewals) : null,
         paymentFrequency,
-        memo
+        memo,
+        null,
+        parseInt(options.holdSeconds)
       );
 
       const tx = new anchor.web3.Transaction().add(instruction);
@@ -252,6 +259,14 @@ program
     "-u, --user-payment <pubkey>",
     "User payment account public key"
   )
+  .option(
+    "--amount <number>",
+    "Amount to pull from a variable policy (in token base units)"
+  )
+  .option(
+    "--invoice-reference <hex>",
+    "Invoice reference of up to 32 bytes stored with the payment record"
+  )
   .action(async (options) => {
     try {
       const sdk = createSDK(
@@ -260,7 +275,25 @@ program
       );
       const userPaymentPda = new PublicKey(options.userPayment);
 
-      const instructions = await sdk.executePayment(userPaymentPda);
+      let invoiceReference: number[] | null = null;
+      if (options.invoiceReference) {
+        const bytes = Buffer.from(options.invoiceReference, "hex");
+        if (bytes.length > 32) {
+          throw new Error("Invoice reference must be at most 32 bytes");
+        }
+        invoiceReference = new Array(32).fill(0);
+        bytes.forEach((byte, i) => (invoiceReference![i] = byte));
+      }
+
+      const instructions = await sdk.executePayment(
+        userPaymentPda,
+        undefined,
+        undefined,
+        undefined,
+        undefined,
+        options.amount ? new anchor.BN(options.amount) : null,
+        invoiceReference
+      );
       const tx = new anchor.web3.Transaction();
       instructions.map((instruction) => tx.add(instruction));
       const signature = await sdk.provider.sendAndConfirm(tx);
@@ -602,4 +635,38 @@ program
     }
   });
 
+program
+  .command("set-config-role")
+  .description("Assign a config role, as the admin")
+  .requiredOption(
+    "-r, --role <role>",
+    "admin, pauser, feeManager, gatewayApprover or compliance"
+  )
+  .requiredOption(
+    "-h, --holder <pubkey>",
+    "New holder, the default public key hands the role back to the admin"
+  )
+  .action(async (options) => {
+    try {
+      const sdk = createSDK(
+        program.opts().connectionUrl,
+        program.opts().keypath
+      );
+      const holder = new PublicKey(options.holder);
+
+      const instruction = await sdk.setConfigRole(
+        { [options.role]: {} } as any,
+        holder
+      );
+      const tx = new anchor.web3.Transaction().add(instruction);
+      const signature = await sdk.provider.sendAndConfirm(tx);
+
+      console.log("Config role assigned successfully!");
+      console.log("Transaction signature:", signature);
+    } catch (error) {
+      console.error("Error assigning config role:", error);
+      process.exit(1);
+    }
+  });
+
 program.parse();
@@ -8,3 +8,36 @@ pub const USER_PAYMENT_SEED: &[u8] = b"user_payment";
 pub const GATEWAY_SEED: &[u8] = b"gateway";
 pub const PAYMENT_POLICY_SEED: &[u8] = b"payment_policy";
 pub const PAYMENTS_SEED: &[u8] = b"payments";
+pub const GATEWAY_BOND_SEED: &[u8] = b"gateway_bond";
+pub const GATEWAY_SIGNERS_SEED: &[u8] = b"gateway_signers";
+pub const ESCROW_SEED: &[u8] = b"escrow";
+pub const ESCROW_VAULT_SEED: &[u8] = b"escrow_vault";
+pub const USER_VAULT_SEED: &[u8] = b"user_vault";
+pub const SOL_VAULT_SEED: &[u8] = b"sol_vault";
+pub const USAGE_REPORT_SEED: &[u8] = b"usage_report";
+pub const RECIPIENT_CAP_SEED: &[u8] = b"recipient_cap";
+pub const RECIPIENT_SETTINGS_SEED: &[u8] = b"recipient_settings";
+pub const MERCHANT_SEED: &[u8] = b"merchant";
+pub const DENYLIST_SEED: &[u8] = b"denylist";
+pub const PENDING_CONFIG_CHANGE_SEED: &[u8] = b"pending_config_change";
+
+/// Program that owns Pyth `PriceUpdateV2` accounts
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+/// Decimals of fiat amounts, e.g. 10_000_000 is $10
+pub const FIAT_DECIMALS: u32 = 6;
+/// Oldest price a fiat-priced payment converts at
+pub const MAX_PRICE_AGE_SECONDS: i64 = 60;
+/// Widest confidence interval a price may have, relative to the price
+pub const MAX_PRICE_CONFIDENCE_BPS: u64 = 200;
+/// Longest a payment can be held in escrow before it is released
+pub const MAX_HOLD_SECONDS: u32 = 90 * 24 * 60 * 60;
+/// Largest bounty for closing a finished policy, a fraction of its rent
+pub const MAX_CLOSE_POLICY_BOUNTY_LAMPORTS: u64 = 1_000_000;
+/// Delay before a queued config change can be applied, for new deployments
+pub const DEFAULT_CONFIG_TIMELOCK_SECONDS: i64 = 2 * 24 * 60 * 60;
+/// Shortest timelock, leaves gateways and merchants time to react to a change
+pub const MIN_CONFIG_TIMELOCK_SECONDS: i64 = 60 * 60;
+/// Longest timelock, keeps a mistaken change from locking the config for good
+pub const MAX_CONFIG_TIMELOCK_SECONDS: i64 = 30 * 24 * 60 * 60;
+/// How long a queued config change stays executable once its timelock has elapsed
+pub const CONFIG_CHANGE_EXPIRY_SECONDS: i64 = 7 * 24 * 60 * 60;
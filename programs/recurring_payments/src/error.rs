@@ -34,4 +34,116 @@ pub enum RecurringPaymentsError {
     InvalidPaymentDueDate,
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
+    #[msg("Gateway still has active payment policies")]
+    GatewayHasActivePolicies,
+    #[msg("Invalid gateway")]
+    InvalidGateway,
+    #[msg("Gateway registration is disabled")]
+    GatewayRegistrationDisabled,
+    #[msg("Gateway bond is still locked")]
+    BondLocked,
+    #[msg("Maximum gateway signers reached")]
+    MaxSignersReached,
+    #[msg("Gateway signer not found")]
+    SignerNotFound,
+    #[msg("Payment is not overdue long enough for keeper execution")]
+    PaymentNotOverdue,
+    #[msg("Keeper token account is missing")]
+    KeeperAccountMissing,
+    #[msg("Invalid payment record")]
+    InvalidRecordId,
+    #[msg("Refund exceeds the amount paid")]
+    RefundExceedsPaid,
+    #[msg("Escrow accounts are missing or not expected")]
+    InvalidEscrow,
+    #[msg("Escrow hold period has ended")]
+    HoldPeriodEnded,
+    #[msg("Escrow hold period has not ended")]
+    HoldPeriodActive,
+    #[msg("Escrow is not in the expected state")]
+    InvalidEscrowStatus,
+    #[msg("Prepaid vault already exists")]
+    VaultAlreadyExists,
+    #[msg("Prepaid vault not found")]
+    VaultNotFound,
+    #[msg("Not supported for native SOL payments")]
+    NotSupportedForSol,
+    #[msg("Payment policy is not a stream")]
+    NotAStream,
+    #[msg("Payment policy is not metered")]
+    NotMetered,
+    #[msg("Reported usage exceeds the payer's cap")]
+    UsageExceedsCap,
+    #[msg("Usage report is required for metered payments")]
+    UsageReportMissing,
+    #[msg("Usage report does not match the billing period")]
+    InvalidUsageReport,
+    #[msg("Amount exceeds the payer's maximum per period")]
+    AmountExceedsMax,
+    #[msg("Amount exceeds the payer's rolling cap")]
+    RollingCapExceeded,
+    #[msg("Invalid price feed")]
+    InvalidPriceFeed,
+    #[msg("Price is stale")]
+    StalePrice,
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Price deviates too far from its average")]
+    PriceSlippageExceeded,
+    #[msg("Payment exceeds the payer's spending limit")]
+    SpendingLimitExceeded,
+    #[msg("Payment exceeds the payer's cap for this recipient")]
+    RecipientCapExceeded,
+    #[msg("User payment still has active policies")]
+    UserPaymentHasActivePolicies,
+    #[msg("Vault still holds funds")]
+    VaultNotEmpty,
+    #[msg("Payment policy has not finished")]
+    PolicyNotFinished,
+    #[msg("Payment policy is cancelled")]
+    PolicyCancelled,
+    #[msg("Payment policy has reached its end date")]
+    PolicyExpired,
+    #[msg("Pause exceeds the longest pause the plan allows")]
+    PauseTooLong,
+    #[msg("Payment policy is suspended by the recipient")]
+    PolicySuspended,
+    #[msg("Invalid payment policy status transition")]
+    InvalidStatusTransition,
+    #[msg("Payment policy is waiting for the recipient to accept it")]
+    PolicyPendingAcceptance,
+    #[msg("Maximum merchant settlement accounts reached")]
+    MaxSettlementAccountsReached,
+    #[msg("Merchant settlement account not found")]
+    SettlementAccountNotFound,
+    #[msg("Merchant is not verified by this gateway")]
+    MerchantNotVerifiedByGateway,
+    #[msg("Payer is on the denylist")]
+    PayerDenylisted,
+    #[msg("Recipient is on the denylist")]
+    RecipientDenylisted,
+    #[msg("Payment policy does not involve the denylisted address")]
+    PolicyNotDenylisted,
+    #[msg("Config change timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Config change is already executable")]
+    TimelockElapsed,
+    #[msg("Gateway policy count has not been backfilled")]
+    GatewayPolicyCountIncomplete,
+    #[msg("Gateway bond is below the required amount")]
+    InsufficientBond,
+    #[msg("Payment is too old to be refunded")]
+    RefundWindowClosed,
+    #[msg("Invalid hold period")]
+    InvalidHoldPeriod,
+    #[msg("Destination would not be rent exempt after the transfer")]
+    DestinationNotRentExempt,
+    #[msg("Payment policy is suspended for compliance")]
+    PolicyComplianceSuspended,
+    #[msg("Payment policy still has held or disputed escrows")]
+    PolicyHasOpenEscrows,
+    #[msg("Gateway bond mint can not change while gateway bonds are posted")]
+    GatewayBondsOutstanding,
+    #[msg("Config change expired or its queuer no longer holds the role")]
+    ConfigChangeLapsed,
 }
@@ -0,0 +1,49 @@
+use crate::{
+    constants::*, error::RecurringPaymentsError,
+    instructions::accept_policy::accept_pending_policy, state::*,
+};
+use anchor_lang::prelude::*;
+
+/// Accepts pending policies in bulk, e.g. for recipients that approve
+/// automatically. The policies are passed as writable remaining accounts.
+#[derive(Accounts)]
+pub struct AcceptPolicies<'info> {
+    pub recipient: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_accept_policies(ctx: Context<AcceptPolicies>) -> Result<()> {
+    let recipient = ctx.accounts.recipient.key();
+    let clock = Clock::get()?;
+
+    for policy_info in ctx.remaining_accounts.iter() {
+        require!(
+            policy_info.owner == &crate::ID && policy_info.is_writable,
+            RecurringPaymentsError::PolicyNotFound
+        );
+
+        let mut data = policy_info.try_borrow_mut_data()?;
+        let mut payment_policy = PaymentPolicy::try_deserialize(&mut &data[..])?;
+        accept_pending_policy(
+            &mut payment_policy,
+            policy_info.key(),
+            &recipient,
+            clock.unix_timestamp,
+        )?;
+        payment_policy.try_serialize(&mut &mut data[..])?;
+    }
+
+    msg!(
+        "Accepted {} payment policies for recipient {:?}",
+        ctx.remaining_accounts.len(),
+        recipient
+    );
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptPolicy<'info> {
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+    )]
+    pub payment_policy: Account<'info, PaymentPolicy>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Activate a policy that is waiting for `recipient` to accept it
+pub(crate) fn accept_pending_policy(
+    payment_policy: &mut PaymentPolicy,
+    payment_policy_key: Pubkey,
+    recipient: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    require_keys_eq!(
+        payment_policy.recipient,
+        *recipient,
+        RecurringPaymentsError::Unauthorized
+    );
+    require!(
+        payment_policy.status == PaymentStatus::PendingAcceptance,
+        RecurringPaymentsError::InvalidStatusTransition
+    );
+
+    payment_policy.status = PaymentStatus::Active;
+    payment_policy.updated_at = now;
+
+    emit!(PaymentPolicyAccepted {
+        payment_policy: payment_policy_key,
+        recipient: *recipient,
+    });
+
+    Ok(())
+}
+
+pub fn handler_accept_policy(ctx: Context<AcceptPolicy>) -> Result<()> {
+    let payment_policy_key = ctx.accounts.payment_policy.key();
+    let clock = Clock::get()?;
+
+    accept_pending_policy(
+        &mut ctx.accounts.payment_policy,
+        payment_policy_key,
+        &ctx.accounts.recipient.key(),
+        clock.unix_timestamp,
+    )?;
+
+    msg!(
+        "Payment policy ID: {} accepted by recipient",
+        ctx.accounts.payment_policy.policy_id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AddGatewaySigner<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GATEWAY_SEED, authority.key().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.authority == authority.key()
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SIGNERS_SEED, gateway.key().as_ref()],
+        bump = gateway_signers.bump,
+    )]
+    pub gateway_signers: Account<'info, GatewaySigners>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_add_gateway_signer(
+    ctx: Context<AddGatewaySigner>,
+    signer: Pubkey,
+    role: GatewaySignerRole,
+    expires_at: i64,
+) -> Result<()> {
+    let gateway_signers = &mut ctx.accounts.gateway_signers;
+    let clock = Clock::get()?;
+
+    require!(
+        expires_at == 0 || expires_at > clock.unix_timestamp,
+        RecurringPaymentsError::InvalidInterval
+    );
+
+    // Make room by dropping keys that have already expired
+    gateway_signers
+        .signers
+        .retain(|entry| entry.is_valid(clock.unix_timestamp));
+
+    let entry = GatewaySignerEntry {
+        signer,
+        role,
+        expires_at,
+    };
+    match gateway_signers
+        .signers
+        .iter_mut()
+        .find(|existing| existing.signer == signer)
+    {
+        Some(existing) => *existing = entry,
+        None => {
+            require!(
+                gateway_signers.signers.len() < GatewaySigners::MAX_SIGNERS,
+                RecurringPaymentsError::MaxSignersReached
+            );
+            gateway_signers.signers.push(entry);
+        }
+    }
+
+    emit!(GatewaySignerAdded {
+        gateway: gateway_signers.gateway,
+        signer,
+        role,
+        expires_at,
+    });
+
+    msg!(
+        "Gateway signer {:?} added as {:?} for gateway: {:?}",
+        signer,
+        role,
+        gateway_signers.gateway
+    );
+
+    Ok(())
+}
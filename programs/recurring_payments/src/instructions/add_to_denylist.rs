@@ -0,0 +1,55 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct AddToDenylist<'info> {
+    #[account(mut)]
+    pub compliance: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&compliance.key(), ConfigRole::Compliance) @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = compliance,
+        space = DenylistEntry::SIZE,
+        seeds = [DENYLIST_SEED, address.as_ref()],
+        bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_add_to_denylist(
+    ctx: Context<AddToDenylist>,
+    address: Pubkey,
+    reason_code: u16,
+) -> Result<()> {
+    let denylist_entry = &mut ctx.accounts.denylist_entry;
+    let clock = Clock::get()?;
+
+    denylist_entry.address = address;
+    denylist_entry.reason_code = reason_code;
+    denylist_entry.added_by = ctx.accounts.compliance.key();
+    denylist_entry.created_at = clock.unix_timestamp;
+    denylist_entry.bump = ctx.bumps.denylist_entry;
+
+    emit!(AddressDenylisted {
+        address,
+        reason_code,
+    });
+
+    msg!(
+        "Address {:?} added to the denylist, reason code: {}",
+        address,
+        reason_code
+    );
+
+    Ok(())
+}
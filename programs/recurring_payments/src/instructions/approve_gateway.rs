@@ -0,0 +1,42 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ApproveGateway<'info> {
+    pub gateway_approver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.authority.as_ref()],
+        bump = gateway.bump,
+        constraint = !gateway.is_active @ RecurringPaymentsError::InvalidGateway,
+        constraint = gateway.bond_unlocks_at == 0 @ RecurringPaymentsError::InvalidGateway,
+        constraint = gateway.bond_mint == Pubkey::default()
+            || (gateway.bond_mint == config.gateway_bond_mint
+                && gateway.bond_amount >= config.gateway_bond_amount) @ RecurringPaymentsError::InsufficientBond,
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&gateway_approver.key(), ConfigRole::GatewayApprover) @ RecurringPaymentsError::Unauthorized,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_approve_gateway(ctx: Context<ApproveGateway>) -> Result<()> {
+    let gateway = &mut ctx.accounts.gateway;
+
+    gateway.is_active = true;
+
+    emit!(GatewayApproved {
+        gateway: gateway.key(),
+        authority: gateway.authority,
+    });
+
+    msg!("Payment gateway approved: {:?}", gateway.key());
+
+    Ok(())
+}
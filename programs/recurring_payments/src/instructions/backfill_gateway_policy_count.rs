@@ -0,0 +1,80 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+/// Adds policies created before gateways counted their policies to the
+/// gateway's policy count. The policies are passed as writable remaining
+/// accounts. Once every policy is counted, `complete` marks the count as
+/// covering the whole gateway.
+#[derive(Accounts)]
+pub struct BackfillGatewayPolicyCount<'info> {
+    pub gateway_approver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.authority.as_ref()],
+        bump = gateway.bump,
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&gateway_approver.key(), ConfigRole::GatewayApprover) @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_backfill_gateway_policy_count(
+    ctx: Context<BackfillGatewayPolicyCount>,
+    complete: bool,
+) -> Result<()> {
+    let gateway_key = ctx.accounts.gateway.key();
+
+    let mut counted = 0u32;
+    for policy_info in ctx.remaining_accounts.iter() {
+        require!(
+            policy_info.owner == &crate::ID && policy_info.is_writable,
+            RecurringPaymentsError::PolicyNotFound
+        );
+
+        let mut data = policy_info.try_borrow_mut_data()?;
+        let mut payment_policy = PaymentPolicy::try_deserialize(&mut &data[..])?;
+        require!(
+            payment_policy.gateway == gateway_key,
+            RecurringPaymentsError::InvalidGateway
+        );
+
+        // Already counted policies are skipped so batches can be retried
+        if payment_policy.counted_by_gateway {
+            continue;
+        }
+        payment_policy.counted_by_gateway = true;
+        payment_policy.try_serialize(&mut &mut data[..])?;
+        counted += 1;
+    }
+
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.active_policies_count = gateway
+        .active_policies_count
+        .checked_add(counted)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+    if complete {
+        gateway.policy_count_complete = true;
+    }
+
+    emit!(GatewayPolicyCountBackfilled {
+        gateway: gateway_key,
+        counted,
+        active_policies_count: gateway.active_policies_count,
+        policy_count_complete: gateway.policy_count_complete,
+    });
+
+    msg!(
+        "Backfilled {} payment policies for gateway {:?}, now counting {}",
+        counted,
+        gateway_key,
+        gateway.active_policies_count
+    );
+
+    Ok(())
+}
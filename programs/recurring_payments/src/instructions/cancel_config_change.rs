@@ -0,0 +1,52 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+/// Drops a queued config change. The role that queued it can cancel it until
+/// it is executed, anyone can clear a change that has lapsed.
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [PENDING_CONFIG_CHANGE_SEED, &[pending_config_change.change.kind()]],
+        bump = pending_config_change.bump,
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    /// CHECK: Receives the rent of the pending change
+    #[account(mut, address = pending_config_change.queued_by)]
+    pub queued_by: UncheckedAccount<'info>,
+}
+
+pub fn handler_cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+    let pending_config_change = &ctx.accounts.pending_config_change;
+    let clock = Clock::get()?;
+
+    let config = &ctx.accounts.config;
+    require!(
+        config.has_role(
+            &ctx.accounts.authority.key(),
+            pending_config_change.change.required_role()
+        ) || !pending_config_change.is_live(config, clock.unix_timestamp),
+        RecurringPaymentsError::Unauthorized
+    );
+
+    let change = pending_config_change.change.clone();
+
+    emit!(ConfigChangeCancelled {
+        change: change.clone(),
+        cancelled_by: ctx.accounts.authority.key(),
+    });
+
+    msg!("Config change {:?} cancelled", change);
+
+    Ok(())
+}
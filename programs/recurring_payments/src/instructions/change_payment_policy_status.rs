@@ -25,6 +25,10 @@ pub struct ChangePaymentPolicyStatus<'info> {
             policy_id.to_le_bytes().as_ref()
         ],
         bump = payment_policy.bump,
+        constraint = payment_policy.status != PaymentStatus::Cancelled @ RecurringPaymentsError::PolicyCancelled,
+        constraint = payment_policy.status != PaymentStatus::Suspended @ RecurringPaymentsError::PolicySuspended,
+        constraint = payment_policy.status != PaymentStatus::ComplianceSuspended @ RecurringPaymentsError::PolicyComplianceSuspended,
+        constraint = payment_policy.status != PaymentStatus::PendingAcceptance @ RecurringPaymentsError::PolicyPendingAcceptance,
     )]
     pub payment_policy: Account<'info, PaymentPolicy>,
 
@@ -45,6 +49,24 @@ pub fn handler_change_payment_policy_status(
     let user_payment = &mut ctx.accounts.user_payment;
     let clock = Clock::get()?;
 
+    // Only the recipient can suspend or accept a policy
+    require!(
+        new_status != PaymentStatus::Suspended
+            && new_status != PaymentStatus::PendingAcceptance
+            && new_status != PaymentStatus::ComplianceSuspended,
+        RecurringPaymentsError::InvalidStatusTransition
+    );
+
+    // Resuming early from a scheduled pause still skips the periods missed so far
+    if payment_policy.paused_until > 0 {
+        if new_status == PaymentStatus::Active {
+            payment_policy
+                .policy_type
+                .skip_periods_until(clock.unix_timestamp)?;
+        }
+        payment_policy.paused_until = 0;
+    }
+
     // Update the policy status
     let old_status = payment_policy.status.clone();
     payment_policy.status = new_status.clone();
@@ -0,0 +1,94 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*, utils::release_gateway_policy};
+use anchor_lang::prelude::*;
+
+/// Permissionless crank that closes policies which will never charge again.
+/// The rent goes back to the payer, minus the configured bounty for the caller.
+#[derive(Accounts)]
+pub struct CloseFinishedPolicy<'info> {
+    #[account(mut)]
+    pub closer: Signer<'info>,
+
+    /// CHECK: The payer, receives the policy's rent
+    #[account(mut, address = user_payment.owner)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_PAYMENT_SEED, user_payment.owner.as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [PAYMENT_POLICY_SEED, user_payment.key().as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+        constraint = payment_policy.open_escrows == 0 @ RecurringPaymentsError::PolicyHasOpenEscrows,
+    )]
+    pub payment_policy: Account<'info, PaymentPolicy>,
+
+    /// CHECK: The gateway of the policy. It may already have been deleted.
+    #[account(
+        mut,
+        address = payment_policy.gateway @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub gateway: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_close_finished_policy(ctx: Context<CloseFinishedPolicy>) -> Result<()> {
+    let payment_policy = &ctx.accounts.payment_policy;
+    let clock = Clock::get()?;
+
+    // Completed, or cancelled with nothing left of the period paid for
+    let is_completed = payment_policy
+        .policy_type
+        .is_finished(payment_policy.payment_count, clock.unix_timestamp)?;
+    let is_cancelled_and_over = payment_policy.status == PaymentStatus::Cancelled
+        && clock.unix_timestamp >= payment_policy.policy_type.period_end();
+    require!(
+        is_completed || is_cancelled_and_over,
+        RecurringPaymentsError::PolicyNotFinished
+    );
+
+    // Pay the bounty out of the rent, the close sends the rest to the payer
+    let policy_info = payment_policy.to_account_info();
+    let bounty_lamports = ctx
+        .accounts
+        .config
+        .close_policy_bounty_lamports
+        .min(policy_info.lamports());
+    if bounty_lamports > 0 {
+        **policy_info.try_borrow_mut_lamports()? -= bounty_lamports;
+        **ctx.accounts.closer.try_borrow_mut_lamports()? += bounty_lamports;
+    }
+
+    let user_payment = &mut ctx.accounts.user_payment;
+    user_payment.active_policies_count = user_payment.active_policies_count.saturating_sub(1);
+    user_payment.updated_at = clock.unix_timestamp;
+
+    release_gateway_policy(&ctx.accounts.gateway, payment_policy)?;
+
+    emit!(FinishedPolicyClosed {
+        payment_policy: payment_policy.key(),
+        owner: user_payment.owner,
+        policy_id: payment_policy.policy_id,
+        closer: ctx.accounts.closer.key(),
+        bounty_lamports,
+    });
+
+    msg!(
+        "Finished payment policy ID: {} closed for user: {:?}",
+        payment_policy.policy_id,
+        user_payment.owner
+    );
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::{constants::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseRecipientSpendingCap<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [RECIPIENT_CAP_SEED, user_payment.key().as_ref(), recipient_spending_cap.recipient.as_ref()],
+        bump = recipient_spending_cap.bump,
+    )]
+    pub recipient_spending_cap: Account<'info, RecipientSpendingCap>,
+}
+
+pub fn handler_close_recipient_spending_cap(ctx: Context<CloseRecipientSpendingCap>) -> Result<()> {
+    let recipient_spending_cap = &ctx.accounts.recipient_spending_cap;
+
+    emit!(RecipientSpendingCapRemoved {
+        user_payment: recipient_spending_cap.user_payment,
+        recipient: recipient_spending_cap.recipient,
+    });
+
+    msg!(
+        "Cap for recipient {:?} removed",
+        recipient_spending_cap.recipient
+    );
+
+    Ok(())
+}
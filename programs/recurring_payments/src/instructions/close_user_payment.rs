@@ -0,0 +1,110 @@
+use crate::{
+    constants::*, error::RecurringPaymentsError,
+    instructions::execute_payment::token_account_has_delegate, state::*,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Revoke, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CloseUserPayment<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+        constraint = user_payment.active_policies_count == 0 @ RecurringPaymentsError::UserPaymentHasActivePolicies,
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    /// Passed to revoke the payments delegate on the way out
+    #[account(
+        mut,
+        address = user_payment.token_account,
+    )]
+    pub token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Prepaid vault, required and closed when the user has one
+    #[account(
+        mut,
+        address = user_payment.vault @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    /// Lamport vault, required when the user pays in native SOL
+    #[account(
+        address = user_payment.vault @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub sol_vault: Option<SystemAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_close_user_payment(ctx: Context<CloseUserPayment>) -> Result<()> {
+    let user_payment = &ctx.accounts.user_payment;
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    // Closing must not strand any funds left in a vault
+    if user_payment.is_native_sol() {
+        let sol_vault = ctx
+            .accounts
+            .sol_vault
+            .as_ref()
+            .ok_or(RecurringPaymentsError::VaultNotFound)?;
+        require!(
+            sol_vault.lamports() == 0,
+            RecurringPaymentsError::VaultNotEmpty
+        );
+    } else if user_payment.has_vault() {
+        let vault = ctx
+            .accounts
+            .vault
+            .as_ref()
+            .ok_or(RecurringPaymentsError::VaultNotFound)?;
+        require!(vault.amount == 0, RecurringPaymentsError::VaultNotEmpty);
+
+        let seeds = &[
+            USER_PAYMENT_SEED,
+            user_payment.owner.as_ref(),
+            user_payment.token_mint.as_ref(),
+            &[user_payment.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = CloseAccount {
+            account: vault.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: user_payment.to_account_info(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
+        token::close_account(cpi_ctx)?;
+    }
+
+    // Revoke the payments delegate if the token account still has it
+    let mut delegate_revoked = false;
+    if let Some(token_account) = &ctx.accounts.token_account {
+        let (payments_delegate, _) = Pubkey::find_program_address(&[PAYMENTS_SEED], &crate::ID);
+        if token_account_has_delegate(token_account, &payments_delegate) {
+            let cpi_accounts = Revoke {
+                source: token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            };
+            token::revoke(CpiContext::new(token_program, cpi_accounts))?;
+            delegate_revoked = true;
+        }
+    }
+
+    emit!(UserPaymentClosed {
+        user_payment: user_payment.key(),
+        owner: user_payment.owner,
+        token_mint: user_payment.token_mint,
+        delegate_revoked,
+    });
+
+    msg!("User payment account closed for: {:?}", user_payment.owner);
+
+    Ok(())
+}
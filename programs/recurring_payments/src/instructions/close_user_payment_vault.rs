@@ -0,0 +1,89 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct CloseUserPaymentVault<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+        constraint = user_payment.funding_mode == FundingMode::TokenVault @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        mut,
+        address = user_payment.vault @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Receives what is left in the vault. Payments are pulled from it through
+    /// the payments delegate once the vault is closed.
+    #[account(
+        mut,
+        address = user_payment.token_account @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_close_user_payment_vault(ctx: Context<CloseUserPaymentVault>) -> Result<()> {
+    let user_payment = &ctx.accounts.user_payment;
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let withdrawn = ctx.accounts.vault.amount;
+
+    // The user payment account owns the vault
+    let seeds = &[
+        USER_PAYMENT_SEED,
+        user_payment.owner.as_ref(),
+        user_payment.token_mint.as_ref(),
+        &[user_payment.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if withdrawn > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: user_payment.to_account_info(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, withdrawn)?;
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.vault.to_account_info(),
+        destination: ctx.accounts.owner.to_account_info(),
+        authority: user_payment.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+    token::close_account(cpi_ctx)?;
+
+    let vault = ctx.accounts.vault.key();
+    let user_payment = &mut ctx.accounts.user_payment;
+    user_payment.vault = Pubkey::default();
+    user_payment.funding_mode = FundingMode::Delegate;
+    user_payment.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(UserPaymentVaultClosed {
+        user_payment: user_payment.key(),
+        owner: user_payment.owner,
+        vault,
+        withdrawn,
+    });
+
+    msg!(
+        "Closed prepaid vault of {:?}, returned {} tokens",
+        user_payment.owner,
+        withdrawn
+    );
+
+    Ok(())
+}
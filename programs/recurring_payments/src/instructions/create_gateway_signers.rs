@@ -0,0 +1,48 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateGatewaySigners<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GATEWAY_SEED, authority.key().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.authority == authority.key()
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = GatewaySigners::SIZE,
+        seeds = [GATEWAY_SIGNERS_SEED, gateway.key().as_ref()],
+        bump
+    )]
+    pub gateway_signers: Account<'info, GatewaySigners>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_create_gateway_signers(ctx: Context<CreateGatewaySigners>) -> Result<()> {
+    let gateway_signers = &mut ctx.accounts.gateway_signers;
+
+    gateway_signers.gateway = ctx.accounts.gateway.key();
+    gateway_signers.signers = Vec::new();
+    gateway_signers.bump = ctx.bumps.gateway_signers;
+
+    msg!(
+        "Gateway signer set created for gateway: {:?}",
+        gateway_signers.gateway
+    );
+
+    Ok(())
+}
@@ -4,14 +4,14 @@ use anchor_lang::prelude::*;
 #[derive(Accounts)]
 pub struct CreatePaymentGateway<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub gateway_approver: Signer<'info>,
 
     /// CHECK: The authority that will own the gateway
     pub authority: UncheckedAccount<'info>,
 
     #[account(
         init,
-        payer = admin,
+        payer = gateway_approver,
         space = PaymentGateway::SIZE,
         seeds = [GATEWAY_SEED, authority.key().as_ref()],
         bump
@@ -21,7 +21,7 @@ pub struct CreatePaymentGateway<'info> {
     #[account(
         seeds = [CONFIG_SEED],
         bump = config.bump,
-        constraint = config.admin == admin.key(),
+        constraint = config.has_role(&gateway_approver.key(), ConfigRole::GatewayApprover) @ RecurringPaymentsError::Unauthorized,
         constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused
     )]
     pub config: Account<'info, ProgramConfig>,
@@ -57,6 +57,13 @@ pub fn handler_create_payment_gateway(
     gateway.name = name;
     gateway.url = url;
     gateway.signer = ctx.accounts.authority.key();
+    gateway.active_policies_count = 0;
+    gateway.policy_count_complete = true;
+    gateway.bond_mint = Pubkey::default();
+    gateway.bond_amount = 0;
+    gateway.bond_unlocks_at = 0;
+    gateway.keeper_grace_seconds = 0;
+    gateway.keeper_bounty_bps = 0;
 
     emit!(PaymentGatewayCreated {
         authority: gateway.authority,
@@ -1,4 +1,9 @@
-use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use crate::{
+    constants::*,
+    error::RecurringPaymentsError,
+    state::*,
+    utils::{recipient_requires_acceptance, require_not_denylisted},
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
 
@@ -19,9 +24,38 @@ pub struct CreatePaymentPolicy<'info> {
     /// corresponding tokenAccount/ata will be derived during execution.
     pub recipient: UncheckedAccount<'info>,
 
+    /// CHECK: The recipient's settings, which may not exist
+    #[account(
+        seeds = [RECIPIENT_SETTINGS_SEED, recipient.key().as_ref()],
+        bump,
+    )]
+    pub recipient_settings: UncheckedAccount<'info>,
+
+    /// CHECK: Denylist entry of the payer, checked when it exists
+    #[account(
+        seeds = [DENYLIST_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub payer_denylist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Denylist entry of the recipient, checked when it exists
+    #[account(
+        seeds = [DENYLIST_SEED, recipient.key().as_ref()],
+        bump,
+    )]
+    pub recipient_denylist_entry: UncheckedAccount<'info>,
+
+    /// The recipient's merchant profile, its verification is recorded in the policy
+    #[account(
+        seeds = [MERCHANT_SEED, recipient.key().as_ref()],
+        bump = merchant.bump,
+    )]
+    pub merchant: Option<Box<Account<'info, Merchant>>>,
+
     pub token_mint: Account<'info, Mint>,
 
     #[account(
+        mut,
         seeds = [GATEWAY_SEED, gateway.authority.as_ref()],
         bump = gateway.bump,
         constraint = gateway.is_active,
@@ -42,7 +76,7 @@ pub struct CreatePaymentPolicy<'info> {
         seeds = [
             PAYMENT_POLICY_SEED,
             user_payment.key().as_ref(),
-            (user_payment.active_policies_count+1).to_le_bytes().as_ref()
+            user_payment.next_policy_id().to_le_bytes().as_ref()
         ],
         bump
     )]
@@ -55,16 +89,92 @@ pub fn handler_create_payment_policy(
     ctx: Context<CreatePaymentPolicy>,
     policy_type: PolicyType,
     memo: [u8; 64],
+    hold_seconds: u32,
 ) -> Result<()> {
+    require_not_denylisted(
+        &ctx.accounts.payer_denylist_entry,
+        RecurringPaymentsError::PayerDenylisted,
+    )?;
+    require_not_denylisted(
+        &ctx.accounts.recipient_denylist_entry,
+        RecurringPaymentsError::RecipientDenylisted,
+    )?;
+
     // Validate the policy type and its parameters
     policy_type.validate()?;
 
+    // The payer chooses how long payments stay disputable in escrow
+    require!(
+        hold_seconds <= MAX_HOLD_SECONDS,
+        RecurringPaymentsError::InvalidHoldPeriod
+    );
+    require!(
+        hold_seconds == 0 || !ctx.accounts.user_payment.is_native_sol(),
+        RecurringPaymentsError::NotSupportedForSol
+    );
+
     let clock = Clock::get()?;
 
     // Adjust next payment due date if in the past
     let mut adjusted_policy_type = policy_type.clone();
     match &mut adjusted_policy_type {
         PolicyType::Subscription {
+            next_payment_due,
+            ends_at,
+            ..
+        } => {
+            if *next_payment_due <= clock.unix_timestamp {
+                msg!("Next payment due date was in the past, adjusting to current timestamp for immediate execution");
+                *next_payment_due = clock.unix_timestamp;
+            }
+            require!(
+                PolicyType::within_term(*next_payment_due, *ends_at),
+                RecurringPaymentsError::PolicyExpired
+            );
+        }
+        PolicyType::Stream {
+            start,
+            end,
+            withdrawn,
+            ..
+        } => {
+            // Nothing vests before the stream is created, keep its duration
+            if *start < clock.unix_timestamp {
+                msg!("Stream start was in the past, shifting the stream to start now");
+                let duration = *end - *start;
+                *start = clock.unix_timestamp;
+                *end = start
+                    .checked_add(duration)
+                    .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+            }
+            *withdrawn = 0;
+        }
+        PolicyType::Metered {
+            period,
+            next_payment_due,
+            ..
+        } => {
+            // The first period starts now at the latest
+            if *next_payment_due <= clock.unix_timestamp {
+                *next_payment_due = crate::utils::calculate_next_payment_due(
+                    clock.unix_timestamp,
+                    period,
+                    clock.unix_timestamp,
+                )?;
+            }
+        }
+        PolicyType::Variable {
+            next_payment_due,
+            recent_amounts,
+            ..
+        } => {
+            if *next_payment_due <= clock.unix_timestamp {
+                msg!("Next payment due date was in the past, adjusting to current timestamp for immediate execution");
+                *next_payment_due = clock.unix_timestamp;
+            }
+            *recent_amounts = [0; 8];
+        }
+        PolicyType::FiatSubscription {
             next_payment_due, ..
         } => {
             if *next_payment_due <= clock.unix_timestamp {
@@ -76,13 +186,24 @@ pub fn handler_create_payment_policy(
 
     let payment_policy = &mut ctx.accounts.payment_policy;
     let user_payment = &mut ctx.accounts.user_payment;
-    let policy_id = user_payment.active_policies_count + 1;
+    let policy_id = user_payment.next_policy_id();
 
     payment_policy.user_payment = user_payment.key();
     payment_policy.recipient = ctx.accounts.recipient.key();
     payment_policy.gateway = ctx.accounts.gateway.key();
     payment_policy.policy_type = adjusted_policy_type;
-    payment_policy.status = PaymentStatus::Active;
+    // The payer picks the price feed of a fiat subscription, so the recipient
+    // has to confirm it before anything is charged
+    let is_fiat = matches!(
+        payment_policy.policy_type,
+        PolicyType::FiatSubscription { .. }
+    );
+    payment_policy.status =
+        if is_fiat || recipient_requires_acceptance(&ctx.accounts.recipient_settings)? {
+            PaymentStatus::PendingAcceptance
+        } else {
+            PaymentStatus::Active
+        };
     payment_policy.memo = memo;
     payment_policy.total_paid = 0;
     payment_policy.payment_count = 0;
@@ -90,6 +211,19 @@ pub fn handler_create_payment_policy(
     payment_policy.updated_at = clock.unix_timestamp;
     payment_policy.policy_id = policy_id;
     payment_policy.bump = ctx.bumps.payment_policy;
+    payment_policy.snapshot_fees(
+        ctx.accounts.gateway.gateway_fee_bps,
+        ctx.accounts.config.protocol_fee_bps,
+    );
+    payment_policy.total_refunded = 0;
+    payment_policy.hold_seconds = hold_seconds;
+    payment_policy.paused_until = 0;
+    payment_policy.max_pause_seconds = 0;
+    payment_policy.merchant_verified_by = ctx
+        .accounts
+        .merchant
+        .as_ref()
+        .and_then(|merchant| merchant.verified_by);
 
     emit!(PaymentPolicyCreated {
         user_payment: payment_policy.user_payment,
@@ -98,6 +232,8 @@ pub fn handler_create_payment_policy(
         policy_id: payment_policy.policy_id,
         policy_type: payment_policy.policy_type.clone(),
         memo: payment_policy.memo,
+        gateway_fee_bps: payment_policy.gateway_fee_bps,
+        protocol_fee_bps: payment_policy.protocol_fee_bps,
     });
 
     // Update user payment account
@@ -108,8 +244,16 @@ pub fn handler_create_payment_policy(
         RecurringPaymentsError::MaxPoliciesReached
     );
     user_payment.active_policies_count = user_payment.active_policies_count.saturating_add(1);
+    user_payment.last_policy_id = policy_id;
     user_payment.updated_at = clock.unix_timestamp;
 
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.active_policies_count = gateway
+        .active_policies_count
+        .checked_add(1)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+    payment_policy.counted_by_gateway = true;
+
     msg!(
         "Payment policy created with ID: {}, recipient: {:?}",
         policy_id,
@@ -0,0 +1,52 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateRecipientSettings<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(
+        init,
+        payer = recipient,
+        space = RecipientSettings::SIZE,
+        seeds = [RECIPIENT_SETTINGS_SEED, recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_settings: Account<'info, RecipientSettings>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_create_recipient_settings(
+    ctx: Context<CreateRecipientSettings>,
+    require_acceptance: bool,
+) -> Result<()> {
+    let recipient_settings = &mut ctx.accounts.recipient_settings;
+    let clock = Clock::get()?;
+
+    recipient_settings.recipient = ctx.accounts.recipient.key();
+    recipient_settings.require_acceptance = require_acceptance;
+    recipient_settings.created_at = clock.unix_timestamp;
+    recipient_settings.updated_at = clock.unix_timestamp;
+    recipient_settings.bump = ctx.bumps.recipient_settings;
+
+    emit!(RecipientSettingsUpdated {
+        recipient: recipient_settings.recipient,
+        require_acceptance,
+    });
+
+    msg!(
+        "Recipient settings created for: {:?}",
+        recipient_settings.recipient
+    );
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct CreateRecipientSpendingCap<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = RecipientSpendingCap::SIZE,
+        seeds = [RECIPIENT_CAP_SEED, user_payment.key().as_ref(), recipient.as_ref()],
+        bump
+    )]
+    pub recipient_spending_cap: Account<'info, RecipientSpendingCap>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_create_recipient_spending_cap(
+    ctx: Context<CreateRecipientSpendingCap>,
+    recipient: Pubkey,
+    limit: u64,
+    period_seconds: i64,
+) -> Result<()> {
+    let recipient_spending_cap = &mut ctx.accounts.recipient_spending_cap;
+    let clock = Clock::get()?;
+
+    require!(limit > 0, RecurringPaymentsError::InvalidAmount);
+
+    recipient_spending_cap.user_payment = ctx.accounts.user_payment.key();
+    recipient_spending_cap.recipient = recipient;
+    recipient_spending_cap.spending_limit = SpendingLimit::new(limit, period_seconds)?;
+    recipient_spending_cap.created_at = clock.unix_timestamp;
+    recipient_spending_cap.bump = ctx.bumps.recipient_spending_cap;
+
+    emit!(RecipientSpendingCapSet {
+        user_payment: recipient_spending_cap.user_payment,
+        recipient,
+        limit,
+        period_seconds,
+    });
+
+    msg!(
+        "Recipient {:?} capped at {} per {}s",
+        recipient,
+        limit,
+        period_seconds
+    );
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*, utils::require_not_denylisted};
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+
+/// Creates a UserPayment that pays in native SOL. Lamports are held in a vault
+/// PDA that policies draw from, so no wrapping is needed.
+#[derive(Accounts)]
+pub struct CreateSolUserPayment<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = UserPayment::SIZE,
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), native_mint::ID.as_ref()],
+        bump
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        seeds = [SOL_VAULT_SEED, user_payment.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    /// CHECK: Denylist entry of the payer, checked when it exists
+    #[account(
+        seeds = [DENYLIST_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub payer_denylist_entry: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_create_sol_user_payment(ctx: Context<CreateSolUserPayment>) -> Result<()> {
+    require_not_denylisted(
+        &ctx.accounts.payer_denylist_entry,
+        RecurringPaymentsError::PayerDenylisted,
+    )?;
+
+    let user_payment = &mut ctx.accounts.user_payment;
+    let clock = Clock::get()?;
+
+    user_payment.owner = ctx.accounts.owner.key();
+    // There is no token account, the owner's wallet takes its place. Vault
+    // withdrawals are paid to the signing owner and refunds to this wallet.
+    user_payment.token_account = ctx.accounts.owner.key();
+    user_payment.token_mint = native_mint::ID;
+    user_payment.active_policies_count = 0;
+    user_payment.last_policy_id = 0;
+    user_payment.created_at = clock.unix_timestamp;
+    user_payment.updated_at = clock.unix_timestamp;
+    user_payment.is_active = true;
+    user_payment.bump = ctx.bumps.user_payment;
+    user_payment.vault = ctx.accounts.sol_vault.key();
+    user_payment.funding_mode = FundingMode::NativeSol;
+    user_payment.spending_limit = SpendingLimit::default();
+
+    emit!(UserPaymentCreated {
+        owner: user_payment.owner,
+        token_account: user_payment.token_account,
+        token_mint: user_payment.token_mint,
+    });
+
+    msg!(
+        "SOL user payment account created for: {:?}",
+        user_payment.owner
+    );
+    Ok(())
+}
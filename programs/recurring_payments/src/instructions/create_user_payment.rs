@@ -1,4 +1,7 @@
-use crate::{error::RecurringPaymentsError, state::*, USER_PAYMENT_SEED};
+use crate::{
+    error::RecurringPaymentsError, state::*, utils::require_not_denylisted, DENYLIST_SEED,
+    USER_PAYMENT_SEED,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount};
 
@@ -23,6 +26,13 @@ pub struct CreateUserPayment<'info> {
     pub token_account: Account<'info, TokenAccount>,
     pub token_mint: Account<'info, Mint>,
 
+    /// CHECK: Denylist entry of the payer, checked when it exists
+    #[account(
+        seeds = [DENYLIST_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub payer_denylist_entry: UncheckedAccount<'info>,
+
     #[account(
         seeds = [b"config"],
         bump = config.bump,
@@ -34,6 +44,11 @@ pub struct CreateUserPayment<'info> {
 }
 
 pub fn handler_create_user_payment(ctx: Context<CreateUserPayment>) -> Result<()> {
+    require_not_denylisted(
+        &ctx.accounts.payer_denylist_entry,
+        RecurringPaymentsError::PayerDenylisted,
+    )?;
+
     let user_payment = &mut ctx.accounts.user_payment;
     let clock = Clock::get()?;
 
@@ -41,10 +56,13 @@ pub fn handler_create_user_payment(ctx: Context<CreateUserPayment>) -> Result<()
     user_payment.token_account = ctx.accounts.token_account.key();
     user_payment.token_mint = ctx.accounts.token_mint.key();
     user_payment.active_policies_count = 0;
+    user_payment.last_policy_id = 0;
     user_payment.created_at = clock.unix_timestamp;
     user_payment.updated_at = clock.unix_timestamp;
     user_payment.is_active = true;
     user_payment.bump = ctx.bumps.user_payment;
+    user_payment.vault = Pubkey::default();
+    user_payment.spending_limit = SpendingLimit::default();
 
     emit!(UserPaymentCreated {
         owner: user_payment.owner,
@@ -0,0 +1,62 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CreateUserPaymentVault<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), token_mint.key().as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+        constraint = !user_payment.has_vault() @ RecurringPaymentsError::VaultAlreadyExists,
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [USER_VAULT_SEED, user_payment.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = user_payment,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_create_user_payment_vault(ctx: Context<CreateUserPaymentVault>) -> Result<()> {
+    let user_payment = &mut ctx.accounts.user_payment;
+    let clock = Clock::get()?;
+
+    user_payment.vault = ctx.accounts.vault.key();
+    user_payment.funding_mode = FundingMode::TokenVault;
+    user_payment.updated_at = clock.unix_timestamp;
+
+    emit!(UserPaymentVaultCreated {
+        user_payment: user_payment.key(),
+        owner: user_payment.owner,
+        vault: user_payment.vault,
+    });
+
+    msg!(
+        "Prepaid vault created for user payment of: {:?}",
+        user_payment.owner
+    );
+
+    Ok(())
+}
@@ -1,38 +1,127 @@
-use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use crate::{
+    constants::*,
+    error::RecurringPaymentsError,
+    state::*,
+    utils::{close_gateway_signers, move_policy_to_gateway},
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct DeletePaymentGateway<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub gateway_approver: Signer<'info>,
 
-    /// CHECK: The authority that owns the gateway
+    /// CHECK: The authority that owns the gateway, receives the rent of its signer set
+    #[account(mut)]
     pub authority: UncheckedAccount<'info>,
 
     #[account(
         mut,
         seeds = [GATEWAY_SEED, authority.key().as_ref()],
         bump = gateway.bump,
-        close = admin
+        constraint = gateway.bond_amount == 0 @ RecurringPaymentsError::BondLocked,
+        constraint = gateway.policy_count_complete @ RecurringPaymentsError::GatewayPolicyCountIncomplete,
+        close = gateway_approver
     )]
     pub gateway: Account<'info, PaymentGateway>,
 
+    /// CHECK: The signer set of the gateway, closed along with it if it exists
+    #[account(
+        mut,
+        seeds = [GATEWAY_SIGNERS_SEED, gateway.key().as_ref()],
+        bump,
+    )]
+    pub gateway_signers: UncheckedAccount<'info>,
+
+    /// The gateway that takes over the remaining policies, passed as writable
+    /// remaining accounts. Required while the deleted gateway still has
+    /// active policies.
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, successor_gateway.authority.as_ref()],
+        bump = successor_gateway.bump,
+        constraint = successor_gateway.is_active @ RecurringPaymentsError::InvalidGateway,
+        constraint = successor_gateway.key() != gateway.key() @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub successor_gateway: Option<Account<'info, PaymentGateway>>,
+
+    /// The successor gateway's authority, agrees to take over the policies
+    pub successor_authority: Option<Signer<'info>>,
+
     #[account(
+        mut,
         seeds = [CONFIG_SEED],
         bump = config.bump,
-        constraint = config.admin == admin.key(),
+        constraint = config.has_role(&gateway_approver.key(), ConfigRole::GatewayApprover) @ RecurringPaymentsError::Unauthorized,
         constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused
     )]
     pub config: Account<'info, ProgramConfig>,
 }
 
 pub fn handler_delete_payment_gateway(ctx: Context<DeletePaymentGateway>) -> Result<()> {
+    let clock = Clock::get()?;
+    let migrated = ctx.remaining_accounts.len() as u32;
+
+    // Live policies must be handed to a successor that agrees to take them
+    let successor = match ctx.accounts.successor_gateway.as_mut() {
+        Some(successor_gateway) => {
+            require!(
+                ctx.accounts
+                    .successor_authority
+                    .as_ref()
+                    .is_some_and(|authority| authority.key() == successor_gateway.authority),
+                RecurringPaymentsError::Unauthorized
+            );
+
+            let mut counted = 0u32;
+            for policy_info in ctx.remaining_accounts.iter() {
+                if move_policy_to_gateway(
+                    policy_info,
+                    &ctx.accounts.gateway,
+                    &successor_gateway.key(),
+                    ctx.accounts.config.protocol_fee_bps,
+                    clock.unix_timestamp,
+                )? {
+                    counted += 1;
+                }
+            }
+
+            successor_gateway.active_policies_count = successor_gateway
+                .active_policies_count
+                .checked_add(migrated)
+                .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+            ctx.accounts.gateway.active_policies_count = ctx
+                .accounts
+                .gateway
+                .active_policies_count
+                .checked_sub(counted)
+                .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+
+            Some(successor_gateway.key())
+        }
+        None => None,
+    };
+
     let gateway = &ctx.accounts.gateway;
+    require!(
+        gateway.active_policies_count == 0,
+        RecurringPaymentsError::GatewayHasActivePolicies
+    );
+
+    close_gateway_signers(&ctx.accounts.gateway_signers, &ctx.accounts.authority)?;
+
+    // A self-registered gateway slashed down to nothing no longer holds a bond
+    if gateway.bond_mint != Pubkey::default() {
+        let config = &mut ctx.accounts.config;
+        config.bonded_gateways = config.bonded_gateways.saturating_sub(1);
+    }
 
     emit!(PaymentGatewayDeleted {
         gateway: gateway.key(),
         authority: gateway.authority,
         name: gateway.name,
+        active_policies_count: migrated,
+        successor,
     });
 
     msg!(
@@ -1,4 +1,4 @@
-use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use crate::{constants::*, error::RecurringPaymentsError, state::*, utils::release_gateway_policy};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -26,10 +26,18 @@ pub struct DeletePaymentPolicy<'info> {
             policy_id.to_le_bytes().as_ref()
         ],
         bump = payment_policy.bump,
+        constraint = payment_policy.open_escrows == 0 @ RecurringPaymentsError::PolicyHasOpenEscrows,
         close = owner
     )]
     pub payment_policy: Account<'info, PaymentPolicy>,
 
+    /// CHECK: The gateway of the policy. It may already have been deleted.
+    #[account(
+        mut,
+        address = payment_policy.gateway @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub gateway: UncheckedAccount<'info>,
+
     #[account(
         seeds = [CONFIG_SEED],
         bump = config.bump,
@@ -53,12 +61,11 @@ pub fn handler_delete_payment_policy(
     });
 
     // Update user payment count (decrease active policies count)
-    user_payment.active_policies_count = user_payment
-        .active_policies_count
-        .checked_sub(1)
-        .unwrap_or(0);
+    user_payment.active_policies_count = user_payment.active_policies_count.saturating_sub(1);
     user_payment.updated_at = clock.unix_timestamp;
 
+    release_gateway_policy(&ctx.accounts.gateway, payment_policy)?;
+
     msg!(
         "Payment policy deleted with ID: {} for user: {:?}",
         payment_policy.policy_id,
@@ -0,0 +1,63 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+};
+
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+        constraint = user_payment.is_native_sol() @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        mut,
+        seeds = [SOL_VAULT_SEED, user_payment.key().as_ref()],
+        bump,
+        address = user_payment.vault @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_deposit_sol(ctx: Context<DepositSol>, amount: u64) -> Result<()> {
+    require!(amount > 0, RecurringPaymentsError::InvalidAmount);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.owner.to_account_info(),
+        to: ctx.accounts.sol_vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, amount)?;
+
+    let balance = ctx.accounts.sol_vault.lamports();
+
+    emit!(VaultDeposited {
+        user_payment: ctx.accounts.user_payment.key(),
+        amount,
+        balance,
+    });
+
+    msg!(
+        "Deposited {} lamports into SOL vault, balance: {}",
+        amount,
+        balance
+    );
+
+    Ok(())
+}
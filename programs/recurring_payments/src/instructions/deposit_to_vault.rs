@@ -0,0 +1,65 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct DepositToVault<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        mut,
+        address = user_payment.vault @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key(),
+        constraint = owner_token_account.mint == user_payment.token_mint,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_deposit_to_vault(ctx: Context<DepositToVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, RecurringPaymentsError::InvalidAmount);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.owner_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    ctx.accounts.vault.reload()?;
+
+    emit!(VaultDeposited {
+        user_payment: ctx.accounts.user_payment.key(),
+        amount,
+        balance: ctx.accounts.vault.amount,
+    });
+
+    msg!(
+        "Deposited {} tokens into prepaid vault, balance: {}",
+        amount,
+        ctx.accounts.vault.amount
+    );
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DeregisterGateway<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, authority.key().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.authority == authority.key(),
+        constraint = gateway.bond_mint != Pubkey::default() @ RecurringPaymentsError::InvalidGateway,
+        constraint = gateway.bond_unlocks_at == 0 @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_deregister_gateway(ctx: Context<DeregisterGateway>) -> Result<()> {
+    let gateway = &mut ctx.accounts.gateway;
+    let clock = Clock::get()?;
+
+    gateway.is_active = false;
+    gateway.bond_unlocks_at = clock
+        .unix_timestamp
+        .checked_add(ctx.accounts.config.gateway_bond_cooldown_seconds)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+
+    emit!(GatewayDeregistered {
+        gateway: gateway.key(),
+        authority: gateway.authority,
+        bond_unlocks_at: gateway.bond_unlocks_at,
+    });
+
+    msg!(
+        "Payment gateway {:?} deregistered, bond unlocks at {}",
+        gateway.key(),
+        gateway.bond_unlocks_at
+    );
+
+    Ok(())
+}
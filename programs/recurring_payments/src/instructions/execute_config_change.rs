@@ -0,0 +1,53 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+/// Applies a queued config change once its timelock has elapsed and before it
+/// lapses. Anyone may execute it, the rent goes back to whoever queued it.
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [PENDING_CONFIG_CHANGE_SEED, &[pending_config_change.change.kind()]],
+        bump = pending_config_change.bump,
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    /// CHECK: Receives the rent of the pending change
+    #[account(mut, address = pending_config_change.queued_by)]
+    pub queued_by: UncheckedAccount<'info>,
+}
+
+pub fn handler_execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let pending_config_change = &ctx.accounts.pending_config_change;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= pending_config_change.executable_at,
+        RecurringPaymentsError::TimelockNotElapsed
+    );
+    require!(
+        pending_config_change.is_live(&ctx.accounts.config, clock.unix_timestamp),
+        RecurringPaymentsError::ConfigChangeLapsed
+    );
+
+    let change = pending_config_change.change.clone();
+    change.apply(&mut ctx.accounts.config);
+
+    emit!(ConfigChangeExecuted {
+        change: change.clone(),
+    });
+
+    msg!("Config change {:?} executed", change);
+
+    Ok(())
+}
@@ -1,8 +1,12 @@
 use crate::{
-    constants::*, error::RecurringPaymentsError, state::*, utils::calculate_next_payment_due,
+    constants::*,
+    error::RecurringPaymentsError,
+    oracle::PriceUpdate,
+    state::*,
+    utils::{charge_recipient_cap, is_gateway_signer, require_not_denylisted, resume_if_due},
 };
 use anchor_lang::{prelude::*, solana_program::program_option::COption};
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 // Add this helper function to your program
 pub fn token_account_has_delegate(
@@ -18,6 +22,7 @@ pub fn token_account_has_delegate(
 #[derive(Accounts)]
 pub struct ExecutePayment<'info> {
     /// CHECK: The gateway authority that can trigger payments
+    #[account(mut)]
     pub fee_payer: Signer<'info>,
 
     #[account(
@@ -31,7 +36,7 @@ pub struct ExecutePayment<'info> {
         mut,
         seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
         bump = payment_policy.bump,
-        constraint = payment_policy.status == PaymentStatus::Active @ crate::error::RecurringPaymentsError::PolicyPaused,
+        constraint = payment_policy.status == PaymentStatus::Active || payment_policy.paused_until > 0 @ crate::error::RecurringPaymentsError::PolicyPaused,
     )]
     pub payment_policy: Box<Account<'info, PaymentPolicy>>,
 
@@ -49,10 +54,15 @@ pub struct ExecutePayment<'info> {
         bump = gateway.bump,
         constraint = gateway.is_active,
         constraint = gateway.key() == payment_policy.gateway,
-        constraint = gateway.signer == fee_payer.key() || user_payment.owner == fee_payer.key(),
     )]
     pub gateway: Box<Account<'info, PaymentGateway>>,
 
+    #[account(
+        seeds = [GATEWAY_SIGNERS_SEED, gateway.key().as_ref()],
+        bump = gateway_signers.bump,
+    )]
+    pub gateway_signers: Option<Box<Account<'info, GatewaySigners>>>,
+
     #[account(
         seeds = [CONFIG_SEED],
         bump = config.bump,
@@ -62,9 +72,9 @@ pub struct ExecutePayment<'info> {
 
     #[account(
         mut,
-        constraint = user_token_account.key() == user_payment.token_account,
+        constraint = user_token_account.key() == user_payment.funding_account(),
         constraint = user_token_account.mint == user_payment.token_mint,
-        constraint = token_account_has_delegate(&user_token_account, &payments_delegate.key()) @ crate::error::RecurringPaymentsError::NoDelegateSet,
+        constraint = user_payment.has_vault() || token_account_has_delegate(&user_token_account, &payments_delegate.key()) @ crate::error::RecurringPaymentsError::NoDelegateSet,
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
@@ -75,6 +85,28 @@ pub struct ExecutePayment<'info> {
     )]
     pub recipient_token_account: Account<'info, TokenAccount>,
 
+    /// CHECK: The payer's cap for the policy recipient, enforced when it exists
+    #[account(
+        mut,
+        seeds = [RECIPIENT_CAP_SEED, user_payment.key().as_ref(), payment_policy.recipient.as_ref()],
+        bump,
+    )]
+    pub recipient_spending_cap: UncheckedAccount<'info>,
+
+    /// CHECK: Denylist entry of the payer, checked when it exists
+    #[account(
+        seeds = [DENYLIST_SEED, user_payment.owner.as_ref()],
+        bump,
+    )]
+    pub payer_denylist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Denylist entry of the recipient, checked when it exists
+    #[account(
+        seeds = [DENYLIST_SEED, payment_policy.recipient.as_ref()],
+        bump,
+    )]
+    pub recipient_denylist_entry: UncheckedAccount<'info>,
+
     #[account(
         mut,
         constraint = gateway_fee_account.mint == user_payment.token_mint,
@@ -89,159 +121,692 @@ pub struct ExecutePayment<'info> {
     )]
     pub protocol_fee_account: Account<'info, TokenAccount>,
 
+    /// Receives the bounty when a keeper executes an overdue payment
+    #[account(
+        mut,
+        constraint = keeper_token_account.mint == user_payment.token_mint,
+        constraint = keeper_token_account.owner == fee_payer.key(),
+    )]
+    pub keeper_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Usage of the closed period, required by metered policies
+    #[account(
+        mut,
+        seeds = [
+            USAGE_REPORT_SEED,
+            payment_policy.key().as_ref(),
+            (payment_policy.payment_count + 1).to_le_bytes().as_ref()
+        ],
+        bump = usage_report.bump,
+    )]
+    pub usage_report: Option<Box<Account<'info, UsageReport>>>,
+
+    /// CHECK: Price update of a fiat-priced policy, validated when it is parsed
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Holds the recipient's share while the policy has a hold period
+    #[account(
+        init,
+        payer = fee_payer,
+        space = PaymentEscrow::SIZE,
+        seeds = [
+            ESCROW_SEED,
+            payment_policy.key().as_ref(),
+            (payment_policy.payment_count + 1).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub escrow: Option<Box<Account<'info, PaymentEscrow>>>,
+
+    #[account(
+        init,
+        payer = fee_payer,
+        seeds = [
+            ESCROW_VAULT_SEED,
+            payment_policy.key().as_ref(),
+            (payment_policy.payment_count + 1).to_le_bytes().as_ref()
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = escrow,
+    )]
+    pub escrow_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(address = user_payment.token_mint)]
+    pub token_mint: Option<Box<Account<'info, Mint>>>,
+
     pub token_program: Program<'info, Token>,
+    pub system_program: Option<Program<'info, System>>,
 }
 
-pub fn handler_execute_payment(ctx: Context<ExecutePayment>) -> Result<()> {
-    let payment_policy = &mut ctx.accounts.payment_policy;
-    let user_payment = &mut ctx.accounts.user_payment;
-    let gateway = &mut ctx.accounts.gateway;
-    let config = &ctx.accounts.config;
-    let clock = Clock::get()?;
+impl<'info> ExecutePayment<'info> {
+    /// Transfer tokens out of the payer's funding account. A prepaid vault is
+    /// owned by the user payment account, otherwise the payments delegate signs.
+    fn transfer_from_user(
+        &self,
+        to: AccountInfo<'info>,
+        amount: u64,
+        delegate_bump: u8,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
 
-    // Get payment details from policy
-    let (payment_amount, current_next_due, payment_frequency) = match &payment_policy.policy_type {
-        PolicyType::Subscription {
-            amount,
-            next_payment_due,
-            payment_frequency,
-            ..
-        } => (*amount, *next_payment_due, payment_frequency),
-    };
+        let cpi_program = self.token_program.to_account_info();
+        if self.user_payment.has_vault() {
+            let cpi_accounts = Transfer {
+                from: self.user_token_account.to_account_info(),
+                to,
+                authority: self.user_payment.to_account_info(),
+            };
+            let seeds = &[
+                USER_PAYMENT_SEED,
+                self.user_payment.owner.as_ref(),
+                self.user_payment.token_mint.as_ref(),
+                &[self.user_payment.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)
+        } else {
+            let cpi_accounts = Transfer {
+                from: self.user_token_account.to_account_info(),
+                to,
+                authority: self.payments_delegate.to_account_info(),
+            };
+            let seeds = &[PAYMENTS_SEED, &[delegate_bump]];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)
+        }
+    }
+}
 
-    // Validate delegated amount is sufficient
-    require!(
-        ctx.accounts.user_token_account.delegated_amount >= payment_amount,
-        RecurringPaymentsError::InsufficientDelegatedAmount
+/// Check that `executor` may trigger a payment due at `next_payment_due`. The
+/// gateway's signers and the payer always may, as may the recipient of a
+/// stream. The amount of a variable pull is chosen by the gateway's signers
+/// or the recipient only. Anyone else may only step in as a keeper once the
+/// payment is overdue, if the gateway allows it. Returns whether the executor
+/// acts as a keeper.
+pub(crate) fn authorize_executor(
+    executor: &Pubkey,
+    payment_policy: &PaymentPolicy,
+    user_payment: &UserPayment,
+    gateway: &PaymentGateway,
+    gateway_signers: Option<&GatewaySigners>,
+    next_payment_due: i64,
+    now: i64,
+) -> Result<bool> {
+    let is_payee_pull = matches!(
+        payment_policy.policy_type,
+        PolicyType::Stream { .. } | PolicyType::Variable { .. }
     );
+    if (is_payee_pull && payment_policy.recipient == *executor)
+        || is_gateway_signer(
+            gateway,
+            gateway_signers,
+            executor,
+            GatewaySignerRole::Executor,
+            now,
+        )
+    {
+        return Ok(false);
+    }
 
-    // Validate payment timing
     require!(
-        clock.unix_timestamp >= current_next_due,
-        crate::error::RecurringPaymentsError::PaymentNotDue
+        !matches!(payment_policy.policy_type, PolicyType::Variable { .. }),
+        RecurringPaymentsError::Unauthorized
     );
+    if user_payment.owner == *executor {
+        return Ok(false);
+    }
 
-    // Check if user has sufficient balance
     require!(
-        ctx.accounts.user_token_account.amount >= payment_amount,
-        crate::error::RecurringPaymentsError::InsufficientBalance
+        gateway.keeper_grace_seconds > 0,
+        RecurringPaymentsError::Unauthorized
+    );
+    let keeper_allowed_at = next_payment_due
+        .checked_add(gateway.keeper_grace_seconds)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+    require!(
+        now >= keeper_allowed_at,
+        RecurringPaymentsError::PaymentNotOverdue
     );
 
-    // Calculate fees
-    let gateway_fee = payment_amount
-        .checked_mul(gateway.gateway_fee_bps as u64)
-        .unwrap()
-        .checked_div(10000)
-        .unwrap();
-
-    let protocol_fee = payment_amount
-        .checked_mul(config.protocol_fee_bps as u64)
-        .unwrap()
-        .checked_div(10000)
-        .unwrap();
-
-    let recipient_amount = payment_amount
-        .checked_sub(gateway_fee)
-        .unwrap()
-        .checked_sub(protocol_fee)
-        .unwrap();
-
-    // Transfer to recipient
-    if recipient_amount > 0 {
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.recipient_token_account.to_account_info(),
-            authority: ctx.accounts.payments_delegate.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let seeds = &[PAYMENTS_SEED, &[ctx.bumps.payments_delegate]];
-        let signer_seeds = &[&seeds[..]];
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, recipient_amount)?;
-    }
+    Ok(true)
+}
 
-    // Transfer gateway fee
-    if gateway_fee > 0 {
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.gateway_fee_account.to_account_info(),
-            authority: ctx.accounts.payments_delegate.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let seeds = &[PAYMENTS_SEED, &[ctx.bumps.payments_delegate]];
-        let signer_seeds = &[&seeds[..]];
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, gateway_fee)?;
-    }
+/// How a payment is split between recipient, gateway, protocol and keeper
+pub(crate) struct PaymentSplit {
+    pub recipient: u64,
+    pub gateway: u64,
+    pub protocol: u64,
+    pub keeper: u64,
+}
 
-    // Transfer protocol fee
-    if protocol_fee > 0 {
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.protocol_fee_account.to_account_info(),
-            authority: ctx.accounts.payments_delegate.to_account_info(),
+impl PaymentSplit {
+    pub(crate) fn new(
+        amount: u64,
+        payment_policy: &PaymentPolicy,
+        gateway: &PaymentGateway,
+        config: &ProgramConfig,
+        is_keeper: bool,
+    ) -> Result<Self> {
+        // Never charge more than the fee rates the payer agreed to
+        let (gateway_fee_bps, protocol_fee_bps) =
+            payment_policy.fee_rates(gateway.gateway_fee_bps, config.protocol_fee_bps);
+
+        let gateway_fee = bps_of(amount, gateway_fee_bps)?;
+        let protocol = bps_of(amount, protocol_fee_bps)?;
+        let recipient = amount
+            .checked_sub(gateway_fee)
+            .and_then(|remaining| remaining.checked_sub(protocol))
+            .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+
+        // A keeper is paid out of the gateway fee
+        let keeper = if is_keeper {
+            bps_of(gateway_fee, gateway.keeper_bounty_bps)?
+        } else {
+            0
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let seeds = &[PAYMENTS_SEED, &[ctx.bumps.payments_delegate]];
-        let signer_seeds = &[&seeds[..]];
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, protocol_fee)?;
+
+        Ok(Self {
+            recipient,
+            gateway: gateway_fee - keeper,
+            protocol,
+            keeper,
+        })
     }
+}
+
+fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    amount
+        .checked_mul(bps as u64)
+        .map(|scaled| scaled / 10000)
+        .ok_or(error!(RecurringPaymentsError::ArithmeticOverflow))
+}
 
-    // Calculate next payment due time based on payment frequency
-    let new_next_due =
-        calculate_next_payment_due(current_next_due, payment_frequency, clock.unix_timestamp)?;
+/// Resolve the amount a payment charges. Metered policies charge the usage
+/// reported for the period that closed at `period_end`, variable pulls the
+/// `requested_amount` within the payer's limits.
+pub(crate) fn resolve_payment_amount(
+    payment_policy: &PaymentPolicy,
+    usage_report: Option<&UsageReport>,
+    requested_amount: Option<u64>,
+    due_amount: u64,
+    period_end: i64,
+) -> Result<u64> {
+    match &payment_policy.policy_type {
+        PolicyType::Metered { .. } => {
+            let usage_report = usage_report.ok_or(RecurringPaymentsError::UsageReportMissing)?;
+            require!(
+                usage_report.period_end == period_end,
+                RecurringPaymentsError::InvalidUsageReport
+            );
+            payment_policy.policy_type.usage_charge(usage_report.units)
+        }
+        PolicyType::Variable { .. } => {
+            let amount = requested_amount.ok_or(RecurringPaymentsError::InvalidAmount)?;
+            payment_policy.policy_type.check_requested_amount(amount)?;
+            Ok(amount)
+        }
+        _ => {
+            require!(
+                requested_amount.is_none(),
+                RecurringPaymentsError::InvalidAmount
+            );
+            Ok(due_amount)
+        }
+    }
+}
 
-    // Update next_payment_due in policy_type
-    match &mut payment_policy.policy_type {
-        PolicyType::Subscription {
-            next_payment_due, ..
+/// Convert the fiat amount of a fiat-priced policy into token base units at
+/// the current oracle price. Other policies keep their `due_amount`.
+pub(crate) fn quote_fiat_amount(
+    payment_policy: &PaymentPolicy,
+    price_feed: Option<&AccountInfo>,
+    token_decimals: Option<u8>,
+    due_amount: u64,
+    now: i64,
+) -> Result<u64> {
+    match &payment_policy.policy_type {
+        PolicyType::FiatSubscription {
+            fiat_amount,
+            price_feed: expected_price_feed,
+            max_slippage_bps,
+            feed_id,
+            ..
         } => {
-            *next_payment_due = new_next_due;
+            let price_feed = price_feed
+                .filter(|price_feed| price_feed.key() == *expected_price_feed)
+                .ok_or(RecurringPaymentsError::InvalidPriceFeed)?;
+            let token_decimals = token_decimals.ok_or(RecurringPaymentsError::InvalidPriceFeed)?;
+
+            let price = PriceUpdate::parse(price_feed, feed_id)?;
+            price.validate(*max_slippage_bps, now)?;
+            price.fiat_to_token_amount(*fiat_amount, token_decimals)
         }
+        _ => Ok(due_amount),
     }
+}
+
+/// Mark a usage report as charged once its payment has been booked
+pub(crate) fn charge_usage_report(
+    usage_report: &mut Account<UsageReport>,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    usage_report.amount_charged = amount;
+    usage_report.charged_at = now;
+
+    emit!(UsageCharged {
+        payment_policy: usage_report.payment_policy,
+        usage_report: usage_report.key(),
+        record_id: usage_report.record_id,
+        units: usage_report.units,
+        amount,
+    });
 
+    Ok(())
+}
+
+/// Book a payment on the policy and gateway once the funds have moved and
+/// emit its payment record
+pub(crate) fn record_payment(
+    payment_policy: &mut Account<PaymentPolicy>,
+    gateway: &mut Account<PaymentGateway>,
+    amount: u64,
+    invoice_reference: Option<[u8; 32]>,
+    now: i64,
+) -> Result<()> {
     // Update payment policy
     payment_policy.total_paid = payment_policy
         .total_paid
-        .checked_add(payment_amount)
-        .unwrap();
-    payment_policy.payment_count = payment_policy.payment_count.checked_add(1).unwrap();
-    payment_policy.updated_at = clock.unix_timestamp;
-
-    // Check if payment count has reached max renewals and set status to Paused
-    match &payment_policy.policy_type {
-        PolicyType::Subscription { max_renewals, .. } => {
-            if let Some(max_renewal) = max_renewals {
-                if payment_policy.payment_count >= *max_renewal {
-                    payment_policy.status = PaymentStatus::Paused;
-                }
-            }
-        }
+        .checked_add(amount)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+    payment_policy.payment_count = payment_policy
+        .payment_count
+        .checked_add(1)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+    let record_id = payment_policy.payment_count;
+    payment_policy.push_receipt(record_id, amount);
+    payment_policy.updated_at = now;
+
+    // Move the schedule forward and set status to Paused once the policy has
+    // run its course, e.g. the payment count reached max renewals
+    let payment_count = payment_policy.payment_count;
+    if payment_policy
+        .policy_type
+        .advance(amount, payment_count, now)?
+    {
+        payment_policy.status = PaymentStatus::Paused;
     }
 
     // Update gateway
-    gateway.total_processed = gateway.total_processed.checked_add(payment_amount).unwrap();
-
-    // Update user payment account
-    user_payment.updated_at = clock.unix_timestamp;
+    gateway.total_processed = gateway
+        .total_processed
+        .checked_add(amount)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
 
     // Emit payment record event
     emit!(PaymentRecord {
         payment_policy: payment_policy.key(),
         gateway: gateway.key(),
-        amount: payment_amount,
-        timestamp: clock.unix_timestamp,
+        amount,
+        timestamp: now,
         memo: payment_policy.memo,
-        record_id: payment_policy.payment_count,
+        record_id,
+        invoice_reference: invoice_reference.unwrap_or_default(),
     });
 
+    Ok(())
+}
+
+pub fn handler_execute_payment(
+    ctx: Context<ExecutePayment>,
+    amount: Option<u64>,
+    invoice_reference: Option<[u8; 32]>,
+) -> Result<()> {
+    require_not_denylisted(
+        &ctx.accounts.payer_denylist_entry,
+        RecurringPaymentsError::PayerDenylisted,
+    )?;
+    require_not_denylisted(
+        &ctx.accounts.recipient_denylist_entry,
+        RecurringPaymentsError::RecipientDenylisted,
+    )?;
+
+    let clock = Clock::get()?;
+
+    // A scheduled pause ends on its own
+    resume_if_due(&mut ctx.accounts.payment_policy, clock.unix_timestamp)?;
+    let accounts = &ctx.accounts;
+    let payment_policy = &accounts.payment_policy;
+    require!(
+        payment_policy.status == PaymentStatus::Active,
+        RecurringPaymentsError::PolicyPaused
+    );
+
+    // Get payment details from policy
+    let (due_amount, current_next_due) = payment_policy
+        .policy_type
+        .due_payment(clock.unix_timestamp)?;
+    let due_amount = quote_fiat_amount(
+        payment_policy,
+        accounts
+            .price_feed
+            .as_ref()
+            .map(|price_feed| price_feed.as_ref()),
+        accounts
+            .token_mint
+            .as_ref()
+            .map(|token_mint| token_mint.decimals),
+        due_amount,
+        clock.unix_timestamp,
+    )?;
+    let payment_amount = resolve_payment_amount(
+        payment_policy,
+        accounts.usage_report.as_deref().map(|report| &**report),
+        amount,
+        due_amount,
+        current_next_due,
+    )?;
+
+    let fee_payer = accounts.fee_payer.key();
+    let is_keeper = authorize_executor(
+        &fee_payer,
+        payment_policy,
+        &accounts.user_payment,
+        &accounts.gateway,
+        accounts
+            .gateway_signers
+            .as_deref()
+            .map(|signers| &**signers),
+        current_next_due,
+        clock.unix_timestamp,
+    )?;
+
+    // Validate delegated amount is sufficient
+    require!(
+        accounts.user_payment.has_vault()
+            || accounts.user_token_account.delegated_amount >= payment_amount,
+        RecurringPaymentsError::InsufficientDelegatedAmount
+    );
+
+    // Validate payment timing. A metered or variable period may close without
+    // a charge, anything else has to charge something.
+    let is_per_period = matches!(
+        payment_policy.policy_type,
+        PolicyType::Metered { .. } | PolicyType::Variable { .. }
+    );
+    require!(
+        clock.unix_timestamp >= current_next_due && (payment_amount > 0 || is_per_period),
+        crate::error::RecurringPaymentsError::PaymentNotDue
+    );
+
+    // Check if user has sufficient balance
+    require!(
+        accounts.user_token_account.amount >= payment_amount,
+        crate::error::RecurringPaymentsError::InsufficientBalance
+    );
+
+    let split = PaymentSplit::new(
+        payment_amount,
+        payment_policy,
+        &accounts.gateway,
+        &accounts.config,
+        is_keeper,
+    )?;
+    let delegate_bump = ctx.bumps.payments_delegate;
+
+    // Transfer to recipient, or into escrow while the policy has a hold period
+    let hold_seconds = payment_policy.hold_seconds;
+    require!(
+        (hold_seconds > 0) == (accounts.escrow.is_some() && accounts.escrow_vault.is_some()),
+        RecurringPaymentsError::InvalidEscrow
+    );
+    match &accounts.escrow_vault {
+        Some(escrow_vault) => accounts.transfer_from_user(
+            escrow_vault.to_account_info(),
+            split.recipient,
+            delegate_bump,
+        )?,
+        None => accounts.transfer_from_user(
+            accounts.recipient_token_account.to_account_info(),
+            split.recipient,
+            delegate_bump,
+        )?,
+    }
+
+    // Transfer gateway fee
+    accounts.transfer_from_user(
+        accounts.gateway_fee_account.to_account_info(),
+        split.gateway,
+        delegate_bump,
+    )?;
+
+    // Transfer protocol fee
+    accounts.transfer_from_user(
+        accounts.protocol_fee_account.to_account_info(),
+        split.protocol,
+        delegate_bump,
+    )?;
+
+    // Transfer keeper bounty
+    if split.keeper > 0 {
+        let keeper_token_account = accounts
+            .keeper_token_account
+            .as_ref()
+            .ok_or(RecurringPaymentsError::KeeperAccountMissing)?;
+        accounts.transfer_from_user(
+            keeper_token_account.to_account_info(),
+            split.keeper,
+            delegate_bump,
+        )?;
+
+        emit!(KeeperBountyPaid {
+            payment_policy: payment_policy.key(),
+            keeper: fee_payer,
+            amount: split.keeper,
+        });
+    }
+
+    let accounts = &mut *ctx.accounts;
+
+    // Enforce the payer's spending limits across all policies
+    require!(
+        accounts
+            .user_payment
+            .spending_limit
+            .try_charge(payment_amount, clock.unix_timestamp)?,
+        RecurringPaymentsError::SpendingLimitExceeded
+    );
+    charge_recipient_cap(
+        &accounts.recipient_spending_cap,
+        payment_amount,
+        clock.unix_timestamp,
+    )?;
+
+    record_payment(
+        &mut accounts.payment_policy,
+        &mut accounts.gateway,
+        payment_amount,
+        invoice_reference,
+        clock.unix_timestamp,
+    )?;
+    if let Some(usage_report) = &mut accounts.usage_report {
+        charge_usage_report(usage_report, payment_amount, clock.unix_timestamp)?;
+    }
+    // An open escrow keeps the policy from being closed until it is settled
+    if accounts.escrow.is_some() {
+        accounts.payment_policy.open_escrows = accounts
+            .payment_policy
+            .open_escrows
+            .checked_add(1)
+            .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+    }
+    let payment_policy = &accounts.payment_policy;
+
+    // Record the escrowed share of this payment
+    if let Some(escrow) = &mut accounts.escrow {
+        escrow.payment_policy = payment_policy.key();
+        escrow.record_id = payment_policy.payment_count;
+        escrow.recipient = payment_policy.recipient;
+        escrow.token_mint = accounts.user_payment.token_mint;
+        escrow.amount = split.recipient;
+        escrow.release_at = clock
+            .unix_timestamp
+            .checked_add(hold_seconds as i64)
+            .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+        escrow.status = EscrowStatus::Held;
+        escrow.rent_payer = fee_payer;
+        escrow.created_at = clock.unix_timestamp;
+        escrow.bump = ctx
+            .bumps
+            .escrow
+            .ok_or(RecurringPaymentsError::InvalidEscrow)?;
+
+        emit!(PaymentEscrowed {
+            payment_policy: escrow.payment_policy,
+            escrow: escrow.key(),
+            record_id: escrow.record_id,
+            amount: escrow.amount,
+            release_at: escrow.release_at,
+        });
+    }
+
+    // Update user payment account
+    accounts.user_payment.updated_at = clock.unix_timestamp;
+
+    // Warn early when a prepaid vault cannot cover the next payment
+    if let Some((next_payment_amount, next_payment_due)) = payment_policy
+        .policy_type
+        .upcoming_payment(payment_amount)?
+    {
+        let balance = accounts
+            .user_token_account
+            .amount
+            .checked_sub(payment_amount)
+            .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+        if accounts.user_payment.has_vault()
+            && payment_policy.status == PaymentStatus::Active
+            && balance < next_payment_amount
+        {
+            emit!(VaultBalanceLow {
+                user_payment: accounts.user_payment.key(),
+                payment_policy: payment_policy.key(),
+                balance,
+                next_payment_amount,
+                next_payment_due,
+            });
+        }
+    }
+
     msg!(
         "Payment executed: {} tokens transferred to recipient, {} gateway fee, {} protocol fee",
-        recipient_amount,
-        gateway_fee,
-        protocol_fee
+        split.recipient,
+        split.gateway + split.keeper,
+        split.protocol
     );
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keeper_gateway(keeper_grace_seconds: i64, keeper_bounty_bps: u16) -> PaymentGateway {
+        let mut gateway: PaymentGateway = zeroed_account(PaymentGateway::SIZE);
+        gateway.signer = Pubkey::new_unique();
+        gateway.gateway_fee_bps = 1000;
+        gateway.keeper_grace_seconds = keeper_grace_seconds;
+        gateway.keeper_bounty_bps = keeper_bounty_bps;
+        gateway
+    }
+
+    #[test]
+    fn keeper_bounty_comes_out_of_gateway_fee() {
+        let mut policy: PaymentPolicy = zeroed_account(PaymentPolicy::SIZE);
+        policy.snapshot_fees(1000, 100);
+        let gateway = keeper_gateway(3600, 5000);
+        let mut config: ProgramConfig = zeroed_account(ProgramConfig::SIZE);
+        config.protocol_fee_bps = 100;
+
+        let split = PaymentSplit::new(10000, &policy, &gateway, &config, true).unwrap();
+        assert_eq!(split.recipient, 8900);
+        assert_eq!(split.gateway, 500);
+        assert_eq!(split.keeper, 500);
+        assert_eq!(split.protocol, 100);
+
+        let split = PaymentSplit::new(10000, &policy, &gateway, &config, false).unwrap();
+        assert_eq!(split.gateway, 1000);
+        assert_eq!(split.keeper, 0);
+    }
+
+    #[test]
+    fn keepers_wait_for_the_grace_period() {
+        let policy: PaymentPolicy = zeroed_account(PaymentPolicy::SIZE);
+        let user_payment: UserPayment = zeroed_account(UserPayment::SIZE);
+        let gateway = keeper_gateway(3600, 5000);
+        let keeper = Pubkey::new_unique();
+        let due = 1_000_000;
+
+        let early = authorize_executor(
+            &keeper,
+            &policy,
+            &user_payment,
+            &gateway,
+            None,
+            due,
+            due + 3599,
+        );
+        assert_eq!(
+            early.unwrap_err(),
+            RecurringPaymentsError::PaymentNotOverdue.into()
+        );
+
+        let late = authorize_executor(
+            &keeper,
+            &policy,
+            &user_payment,
+            &gateway,
+            None,
+            due,
+            due + 3600,
+        );
+        assert!(late.unwrap());
+
+        let signer = authorize_executor(
+            &gateway.signer,
+            &policy,
+            &user_payment,
+            &gateway,
+            None,
+            due,
+            due,
+        );
+        assert!(!signer.unwrap());
+    }
+
+    #[test]
+    fn keepers_need_the_gateway_to_opt_in() {
+        let policy: PaymentPolicy = zeroed_account(PaymentPolicy::SIZE);
+        let user_payment: UserPayment = zeroed_account(UserPayment::SIZE);
+        let gateway = keeper_gateway(0, 0);
+
+        let result = authorize_executor(
+            &Pubkey::new_unique(),
+            &policy,
+            &user_payment,
+            &gateway,
+            None,
+            0,
+            i64::MAX,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            RecurringPaymentsError::Unauthorized.into()
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use crate::{
+    constants::*,
+    error::RecurringPaymentsError,
+    instructions::execute_payment::{
+        authorize_executor, charge_usage_report, quote_fiat_amount, record_payment,
+        resolve_payment_amount, PaymentSplit,
+    },
+    state::*,
+    utils::{charge_recipient_cap, require_not_denylisted, resume_if_due, stays_rent_exempt},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+};
+use anchor_spl::token::spl_token::native_mint;
+
+/// Executes a payment of a native SOL user payment. Lamports are drawn from
+/// the user's SOL vault and fees are paid in lamports.
+#[derive(Accounts)]
+pub struct ExecuteSolPayment<'info> {
+    /// CHECK: The gateway authority that can trigger payments
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+        constraint = payment_policy.status == PaymentStatus::Active || payment_policy.paused_until > 0 @ crate::error::RecurringPaymentsError::PolicyPaused,
+    )]
+    pub payment_policy: Box<Account<'info, PaymentPolicy>>,
+
+    #[account(
+        mut,
+        seeds = [USER_PAYMENT_SEED, user_payment.owner.as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.is_active,
+        constraint = user_payment.key() == payment_policy.user_payment,
+        constraint = user_payment.is_native_sol() @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub user_payment: Box<Account<'info, UserPayment>>,
+
+    #[account(
+        mut,
+        seeds = [SOL_VAULT_SEED, user_payment.key().as_ref()],
+        bump,
+        address = user_payment.vault @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.authority.as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_active,
+        constraint = gateway.key() == payment_policy.gateway,
+    )]
+    pub gateway: Box<Account<'info, PaymentGateway>>,
+
+    #[account(
+        seeds = [GATEWAY_SIGNERS_SEED, gateway.key().as_ref()],
+        bump = gateway_signers.bump,
+    )]
+    pub gateway_signers: Option<Box<Account<'info, GatewaySigners>>>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+
+    /// CHECK: The recipient of the policy, receives lamports
+    #[account(mut, address = payment_policy.recipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: The payer's cap for the policy recipient, enforced when it exists
+    #[account(
+        mut,
+        seeds = [RECIPIENT_CAP_SEED, user_payment.key().as_ref(), payment_policy.recipient.as_ref()],
+        bump,
+    )]
+    pub recipient_spending_cap: UncheckedAccount<'info>,
+
+    /// CHECK: Denylist entry of the payer, checked when it exists
+    #[account(
+        seeds = [DENYLIST_SEED, user_payment.owner.as_ref()],
+        bump,
+    )]
+    pub payer_denylist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Denylist entry of the recipient, checked when it exists
+    #[account(
+        seeds = [DENYLIST_SEED, payment_policy.recipient.as_ref()],
+        bump,
+    )]
+    pub recipient_denylist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: The gateway fee recipient, receives lamports
+    #[account(mut, address = gateway.fee_recipient)]
+    pub gateway_fee_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: The protocol fee recipient, receives lamports
+    #[account(mut, address = config.fee_recipient)]
+    pub protocol_fee_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Price update of a fiat-priced policy, validated when it is parsed
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Usage of the closed period, required by metered policies
+    #[account(
+        mut,
+        seeds = [
+            USAGE_REPORT_SEED,
+            payment_policy.key().as_ref(),
+            (payment_policy.payment_count + 1).to_le_bytes().as_ref()
+        ],
+        bump = usage_report.bump,
+    )]
+    pub usage_report: Option<Box<Account<'info, UsageReport>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteSolPayment<'info> {
+    /// Transfer lamports out of the user's SOL vault
+    fn transfer_from_vault(
+        &self,
+        to: AccountInfo<'info>,
+        amount: u64,
+        vault_bump: u8,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let user_payment_key = self.user_payment.key();
+        let seeds = &[SOL_VAULT_SEED, user_payment_key.as_ref(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: self.sol_vault.to_account_info(),
+            to,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        system_program::transfer(cpi_ctx, amount)
+    }
+}
+
+pub fn handler_execute_sol_payment(
+    ctx: Context<ExecuteSolPayment>,
+    amount: Option<u64>,
+    invoice_reference: Option<[u8; 32]>,
+) -> Result<()> {
+    require_not_denylisted(
+        &ctx.accounts.payer_denylist_entry,
+        RecurringPaymentsError::PayerDenylisted,
+    )?;
+    require_not_denylisted(
+        &ctx.accounts.recipient_denylist_entry,
+        RecurringPaymentsError::RecipientDenylisted,
+    )?;
+
+    let clock = Clock::get()?;
+
+    // A scheduled pause ends on its own
+    resume_if_due(&mut ctx.accounts.payment_policy, clock.unix_timestamp)?;
+    let accounts = &ctx.accounts;
+    let payment_policy = &accounts.payment_policy;
+    require!(
+        payment_policy.status == PaymentStatus::Active,
+        RecurringPaymentsError::PolicyPaused
+    );
+
+    // Escrow vaults are token accounts
+    require!(
+        payment_policy.hold_seconds == 0,
+        RecurringPaymentsError::NotSupportedForSol
+    );
+
+    // Get payment details from policy
+    let (due_amount, current_next_due) = payment_policy
+        .policy_type
+        .due_payment(clock.unix_timestamp)?;
+    let due_amount = quote_fiat_amount(
+        payment_policy,
+        accounts
+            .price_feed
+            .as_ref()
+            .map(|price_feed| price_feed.as_ref()),
+        Some(native_mint::DECIMALS),
+        due_amount,
+        clock.unix_timestamp,
+    )?;
+    let payment_amount = resolve_payment_amount(
+        payment_policy,
+        accounts.usage_report.as_deref().map(|report| &**report),
+        amount,
+        due_amount,
+        current_next_due,
+    )?;
+
+    let fee_payer = accounts.fee_payer.key();
+    let is_keeper = authorize_executor(
+        &fee_payer,
+        payment_policy,
+        &accounts.user_payment,
+        &accounts.gateway,
+        accounts
+            .gateway_signers
+            .as_deref()
+            .map(|signers| &**signers),
+        current_next_due,
+        clock.unix_timestamp,
+    )?;
+
+    // Validate payment timing
+    let is_per_period = matches!(
+        payment_policy.policy_type,
+        PolicyType::Metered { .. } | PolicyType::Variable { .. }
+    );
+    require!(
+        clock.unix_timestamp >= current_next_due && (payment_amount > 0 || is_per_period),
+        RecurringPaymentsError::PaymentNotDue
+    );
+
+    // The vault has to stay rent exempt
+    let rent = Rent::get()?;
+    let available = accounts
+        .sol_vault
+        .lamports()
+        .saturating_sub(rent.minimum_balance(0));
+    require!(
+        available >= payment_amount,
+        RecurringPaymentsError::InsufficientBalance
+    );
+
+    let mut split = PaymentSplit::new(
+        payment_amount,
+        payment_policy,
+        &accounts.gateway,
+        &accounts.config,
+        is_keeper,
+    )?;
+    let vault_bump = ctx.bumps.sol_vault;
+
+    // The runtime rejects transfers leaving an account below rent exemption.
+    // The recipient has to be funded, but a fee too small to fund an empty fee
+    // account is not charged, so that account cannot block the payment.
+    require!(
+        stays_rent_exempt(
+            &accounts.recipient.to_account_info(),
+            split.recipient,
+            &rent
+        ),
+        RecurringPaymentsError::DestinationNotRentExempt
+    );
+    let mut skipped = 0u64;
+    for (destination, fee) in [
+        (
+            accounts.gateway_fee_recipient.to_account_info(),
+            &mut split.gateway,
+        ),
+        (
+            accounts.protocol_fee_recipient.to_account_info(),
+            &mut split.protocol,
+        ),
+        (accounts.fee_payer.to_account_info(), &mut split.keeper),
+    ] {
+        if !stays_rent_exempt(&destination, *fee, &rent) {
+            emit!(SolFeeSkipped {
+                payment_policy: payment_policy.key(),
+                destination: destination.key(),
+                amount: *fee,
+            });
+            skipped += *fee;
+            *fee = 0;
+        }
+    }
+    let payment_amount = payment_amount - skipped;
+
+    accounts.transfer_from_vault(
+        accounts.recipient.to_account_info(),
+        split.recipient,
+        vault_bump,
+    )?;
+    accounts.transfer_from_vault(
+        accounts.gateway_fee_recipient.to_account_info(),
+        split.gateway,
+        vault_bump,
+    )?;
+    accounts.transfer_from_vault(
+        accounts.protocol_fee_recipient.to_account_info(),
+        split.protocol,
+        vault_bump,
+    )?;
+    if split.keeper > 0 {
+        accounts.transfer_from_vault(
+            accounts.fee_payer.to_account_info(),
+            split.keeper,
+            vault_bump,
+        )?;
+
+        emit!(KeeperBountyPaid {
+            payment_policy: payment_policy.key(),
+            keeper: fee_payer,
+            amount: split.keeper,
+        });
+    }
+
+    let accounts = &mut *ctx.accounts;
+
+    // Enforce the payer's spending limits across all policies
+    require!(
+        accounts
+            .user_payment
+            .spending_limit
+            .try_charge(payment_amount, clock.unix_timestamp)?,
+        RecurringPaymentsError::SpendingLimitExceeded
+    );
+    charge_recipient_cap(
+        &accounts.recipient_spending_cap,
+        payment_amount,
+        clock.unix_timestamp,
+    )?;
+
+    record_payment(
+        &mut accounts.payment_policy,
+        &mut accounts.gateway,
+        payment_amount,
+        invoice_reference,
+        clock.unix_timestamp,
+    )?;
+    if let Some(usage_report) = &mut accounts.usage_report {
+        charge_usage_report(usage_report, payment_amount, clock.unix_timestamp)?;
+    }
+    accounts.user_payment.updated_at = clock.unix_timestamp;
+
+    // Warn early when the vault cannot cover the next payment
+    let balance = available - payment_amount;
+    if let Some((next_payment_amount, next_payment_due)) = accounts
+        .payment_policy
+        .policy_type
+        .upcoming_payment(payment_amount)?
+    {
+        if accounts.payment_policy.status == PaymentStatus::Active && balance < next_payment_amount
+        {
+            emit!(VaultBalanceLow {
+                user_payment: accounts.user_payment.key(),
+                payment_policy: accounts.payment_policy.key(),
+                balance,
+                next_payment_amount,
+                next_payment_due,
+            });
+        }
+    }
+
+    msg!(
+        "SOL payment executed: {} lamports transferred to recipient, {} gateway fee, {} protocol fee",
+        split.recipient,
+        split.gateway + split.keeper,
+        split.protocol
+    );
+
+    Ok(())
+}
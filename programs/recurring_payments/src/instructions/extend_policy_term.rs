@@ -0,0 +1,86 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+/// Payer and recipient jointly extend the end date or renewal cap of a
+/// subscription
+#[derive(Accounts)]
+pub struct ExtendPolicyTerm<'info> {
+    pub owner: Signer<'info>,
+
+    pub recipient: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, user_payment.key().as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+        constraint = payment_policy.recipient == recipient.key() @ RecurringPaymentsError::Unauthorized,
+        constraint = payment_policy.status != PaymentStatus::Cancelled @ RecurringPaymentsError::PolicyCancelled,
+    )]
+    pub payment_policy: Account<'info, PaymentPolicy>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_extend_policy_term(
+    ctx: Context<ExtendPolicyTerm>,
+    new_ends_at: i64,
+    new_max_renewals: Option<u32>,
+) -> Result<()> {
+    let payment_policy = &mut ctx.accounts.payment_policy;
+    let clock = Clock::get()?;
+
+    match &mut payment_policy.policy_type {
+        PolicyType::Subscription {
+            max_renewals,
+            ends_at,
+            ..
+        } => {
+            // 0 lifts the bound, a new bound may only lie further out
+            require!(
+                new_ends_at == 0
+                    || (new_ends_at > clock.unix_timestamp
+                        && *ends_at != 0
+                        && new_ends_at >= *ends_at),
+                RecurringPaymentsError::InvalidInterval
+            );
+            require!(
+                new_max_renewals.is_none_or(|new_max_renewals| {
+                    max_renewals.is_some_and(|max_renewals| new_max_renewals >= max_renewals)
+                }),
+                RecurringPaymentsError::InvalidInterval
+            );
+
+            *ends_at = new_ends_at;
+            *max_renewals = new_max_renewals;
+        }
+        _ => return err!(RecurringPaymentsError::InvalidInterval),
+    }
+    payment_policy.updated_at = clock.unix_timestamp;
+
+    emit!(PaymentPolicyTermExtended {
+        payment_policy: payment_policy.key(),
+        ends_at: new_ends_at,
+        max_renewals: new_max_renewals,
+    });
+
+    msg!(
+        "Payment policy ID: {} term extended to ends_at: {}, max_renewals: {:?}",
+        payment_policy.policy_id,
+        new_ends_at,
+        new_max_renewals
+    );
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::{state::*, CONFIG_SEED};
+use crate::{state::*, CONFIG_SEED, DEFAULT_CONFIG_TIMELOCK_SECONDS};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -27,6 +27,16 @@ pub fn handle_initialize(ctx: Context<Initialize>) -> Result<()> {
     config.max_policies_per_user = 10;
     config.emergency_pause = false;
     config.bump = ctx.bumps.config;
+    config.gateway_bond_mint = Pubkey::default();
+    config.gateway_bond_amount = 0;
+    config.gateway_bond_cooldown_seconds = 0;
+    config.close_policy_bounty_lamports = 0;
+    config.pauser = Pubkey::default();
+    config.fee_manager = Pubkey::default();
+    config.gateway_approver = Pubkey::default();
+    config.compliance = Pubkey::default();
+    config.timelock_seconds = DEFAULT_CONFIG_TIMELOCK_SECONDS;
+    config.bonded_gateways = 0;
 
     emit!(ProgramConfigCreated {
         admin: config.admin,
@@ -0,0 +1,98 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*, utils::require_not_denylisted};
+use anchor_lang::prelude::*;
+
+/// Lifts the compliance suspension of policies in bulk, once the parties are
+/// cleared. Each policy is passed as four remaining accounts: the writable
+/// policy, its user payment, and the denylist entries of the payer and the
+/// recipient, which must have been removed. Policies the recipient had
+/// suspended go back to `Suspended`, the others to the payer as `Paused`.
+#[derive(Accounts)]
+pub struct LiftComplianceSuspension<'info> {
+    pub compliance: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&compliance.key(), ConfigRole::Compliance) @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_lift_compliance_suspension(ctx: Context<LiftComplianceSuspension>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let groups = ctx.remaining_accounts.chunks_exact(4);
+    require!(
+        groups.remainder().is_empty(),
+        RecurringPaymentsError::PolicyNotFound
+    );
+
+    for accounts in groups {
+        let [policy_info, user_payment_info, payer_denylist_entry, recipient_denylist_entry] =
+            accounts
+        else {
+            unreachable!()
+        };
+        require!(
+            policy_info.owner == &crate::ID && policy_info.is_writable,
+            RecurringPaymentsError::PolicyNotFound
+        );
+
+        let mut data = policy_info.try_borrow_mut_data()?;
+        let mut payment_policy = PaymentPolicy::try_deserialize(&mut &data[..])?;
+        require!(
+            payment_policy.status == PaymentStatus::ComplianceSuspended,
+            RecurringPaymentsError::InvalidStatusTransition
+        );
+
+        // Both parties must be off the denylist
+        require!(
+            user_payment_info.key() == payment_policy.user_payment
+                && user_payment_info.owner == &crate::ID,
+            RecurringPaymentsError::PolicyNotFound
+        );
+        let payer =
+            UserPayment::try_deserialize(&mut &user_payment_info.try_borrow_data()?[..])?.owner;
+        for (entry_info, address, error) in [
+            (
+                payer_denylist_entry,
+                payer,
+                RecurringPaymentsError::PayerDenylisted,
+            ),
+            (
+                recipient_denylist_entry,
+                payment_policy.recipient,
+                RecurringPaymentsError::RecipientDenylisted,
+            ),
+        ] {
+            let (entry, _) =
+                Pubkey::find_program_address(&[DENYLIST_SEED, address.as_ref()], &crate::ID);
+            // An entry at another address proves nothing
+            require_keys_eq!(entry_info.key(), entry, error);
+            require_not_denylisted(entry_info, error)?;
+        }
+
+        let new_status = if payment_policy.suspended_by_recipient {
+            PaymentStatus::Suspended
+        } else {
+            PaymentStatus::Paused
+        };
+        payment_policy.status = new_status.clone();
+        payment_policy.suspended_by_recipient = false;
+        payment_policy.updated_at = clock.unix_timestamp;
+        payment_policy.try_serialize(&mut &mut data[..])?;
+
+        emit!(PaymentPolicyStatusChanged {
+            payment_policy: policy_info.key(),
+            old_status: PaymentStatus::ComplianceSuspended,
+            new_status,
+        });
+    }
+
+    msg!(
+        "Lifted the compliance suspension of {} payment policies",
+        ctx.remaining_accounts.len() / 4
+    );
+
+    Ok(())
+}
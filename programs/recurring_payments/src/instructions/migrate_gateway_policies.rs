@@ -0,0 +1,77 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*, utils::move_policy_to_gateway};
+use anchor_lang::prelude::*;
+
+/// Hands over payment policies from one gateway to another. Both gateway
+/// authorities have to sign. The policies are passed as writable remaining
+/// accounts.
+#[derive(Accounts)]
+pub struct MigrateGatewayPolicies<'info> {
+    pub old_authority: Signer<'info>,
+
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, old_authority.key().as_ref()],
+        bump = old_gateway.bump,
+        constraint = old_gateway.authority == old_authority.key(),
+    )]
+    pub old_gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, new_authority.key().as_ref()],
+        bump = new_gateway.bump,
+        constraint = new_gateway.authority == new_authority.key(),
+        constraint = new_gateway.is_active @ RecurringPaymentsError::InvalidGateway,
+        constraint = new_gateway.key() != old_gateway.key() @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub new_gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_migrate_gateway_policies(ctx: Context<MigrateGatewayPolicies>) -> Result<()> {
+    let old_gateway_key = ctx.accounts.old_gateway.key();
+    let new_gateway_key = ctx.accounts.new_gateway.key();
+    let clock = Clock::get()?;
+
+    let mut counted = 0u32;
+    for policy_info in ctx.remaining_accounts.iter() {
+        if move_policy_to_gateway(
+            policy_info,
+            &ctx.accounts.old_gateway,
+            &new_gateway_key,
+            ctx.accounts.config.protocol_fee_bps,
+            clock.unix_timestamp,
+        )? {
+            counted += 1;
+        }
+    }
+
+    let migrated = ctx.remaining_accounts.len() as u32;
+    let old_gateway = &mut ctx.accounts.old_gateway;
+    old_gateway.active_policies_count = old_gateway
+        .active_policies_count
+        .checked_sub(counted)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+    let new_gateway = &mut ctx.accounts.new_gateway;
+    new_gateway.active_policies_count = new_gateway
+        .active_policies_count
+        .checked_add(migrated)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+
+    msg!(
+        "Migrated {} payment policies from gateway {:?} to {:?}",
+        migrated,
+        old_gateway_key,
+        new_gateway_key
+    );
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*, utils::release_gateway_policy};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(policy_id: u32)]
+pub struct MigratePolicyGateway<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), token_mint.key().as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    /// CHECK: This is the token mint for the payment
+    pub token_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            PAYMENT_POLICY_SEED,
+            user_payment.key().as_ref(),
+            policy_id.to_le_bytes().as_ref()
+        ],
+        bump = payment_policy.bump,
+    )]
+    pub payment_policy: Account<'info, PaymentPolicy>,
+
+    /// CHECK: The current gateway of the policy. It may already have been deleted.
+    #[account(
+        mut,
+        address = payment_policy.gateway @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub old_gateway: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, new_gateway.authority.as_ref()],
+        bump = new_gateway.bump,
+        constraint = new_gateway.is_active @ RecurringPaymentsError::InvalidGateway,
+        constraint = new_gateway.key() != old_gateway.key() @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub new_gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_migrate_policy_gateway(
+    ctx: Context<MigratePolicyGateway>,
+    _policy_id: u32,
+) -> Result<()> {
+    let payment_policy = &mut ctx.accounts.payment_policy;
+    let new_gateway = &mut ctx.accounts.new_gateway;
+    let clock = Clock::get()?;
+
+    release_gateway_policy(&ctx.accounts.old_gateway, payment_policy)?;
+    new_gateway.active_policies_count = new_gateway
+        .active_policies_count
+        .checked_add(1)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+
+    // The payer signs this migration and thereby agrees to the new gateway's fee
+    let old_gateway = payment_policy.gateway;
+    payment_policy.gateway = new_gateway.key();
+    payment_policy.counted_by_gateway = true;
+    let (_, protocol_fee_bps) = payment_policy.fee_rates(
+        new_gateway.gateway_fee_bps,
+        ctx.accounts.config.protocol_fee_bps,
+    );
+    payment_policy.snapshot_fees(new_gateway.gateway_fee_bps, protocol_fee_bps);
+    payment_policy.updated_at = clock.unix_timestamp;
+
+    emit!(PaymentPolicyGatewayMigrated {
+        payment_policy: payment_policy.key(),
+        old_gateway,
+        new_gateway: payment_policy.gateway,
+    });
+
+    msg!(
+        "Payment policy ID: {} migrated from gateway {:?} to {:?}",
+        payment_policy.policy_id,
+        old_gateway,
+        payment_policy.gateway
+    );
+
+    Ok(())
+}
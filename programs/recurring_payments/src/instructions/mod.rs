@@ -1,21 +1,147 @@
+pub mod accept_policies;
+pub mod accept_policy;
+pub mod accept_policy_fees;
+pub mod add_gateway_signer;
+pub mod add_to_denylist;
+pub mod approve_gateway;
+pub mod backfill_gateway_policy_count;
+pub mod cancel_config_change;
 pub mod change_gateway_fee_recipient;
 pub mod change_gateway_signer;
 pub mod change_payment_policy_status;
+pub mod close_finished_policy;
+pub mod close_recipient_spending_cap;
+pub mod close_user_payment;
+pub mod close_user_payment_vault;
+pub mod create_gateway_signers;
 pub mod create_payment_gateway;
 pub mod create_payment_policy;
+pub mod create_recipient_settings;
+pub mod create_recipient_spending_cap;
+pub mod create_sol_user_payment;
 pub mod create_user_payment;
+pub mod create_user_payment_vault;
 pub mod delete_payment_gateway;
 pub mod delete_payment_policy;
+pub mod deposit_sol;
+pub mod deposit_to_vault;
+pub mod deregister_gateway;
+pub mod execute_config_change;
 pub mod execute_payment;
+pub mod execute_sol_payment;
+pub mod extend_policy_term;
 pub mod initialize;
+pub mod lift_compliance_suspension;
+pub mod migrate_gateway_policies;
+pub mod migrate_policy_gateway;
+pub mod open_dispute;
+pub mod pause_payment_policy_until;
+pub mod queue_config_change;
+pub mod recipient_set_policy_status;
+pub mod refund_payment;
+pub mod refund_sol_payment;
+pub mod register_gateway;
+pub mod register_merchant;
+pub mod release_escrow;
+pub mod remove_from_denylist;
+pub mod remove_gateway_signer;
+pub mod remove_merchant_settlement_account;
+pub mod report_payment_failure;
+pub mod resolve_dispute;
+pub mod revoke_merchant_verification;
+pub mod set_close_policy_bounty;
+pub mod set_config_role;
+pub mod set_emergency_pause;
+pub mod set_gateway_bond_config;
+pub mod set_gateway_keeper_settings;
+pub mod set_merchant_settlement_account;
+pub mod set_policy_hold_period;
+pub mod set_policy_max_pause;
+pub mod set_recipient_require_acceptance;
+pub mod set_spending_limit;
+pub mod set_user_payment_active;
+pub mod slash_gateway_bond;
+pub mod stop_stream;
+pub mod submit_usage_report;
+pub mod suspend_denylisted_policies;
+pub mod top_up_gateway_bond;
+pub mod update_merchant;
+pub mod update_user_payment_token_account;
+pub mod verify_merchant;
+pub mod withdraw_from_vault;
+pub mod withdraw_gateway_bond;
+pub mod withdraw_sol;
 
+pub use accept_policies::*;
+pub use accept_policy::*;
+pub use accept_policy_fees::*;
+pub use add_gateway_signer::*;
+pub use add_to_denylist::*;
+pub use approve_gateway::*;
+pub use backfill_gateway_policy_count::*;
+pub use cancel_config_change::*;
 pub use change_gateway_fee_recipient::*;
 pub use change_gateway_signer::*;
 pub use change_payment_policy_status::*;
+pub use close_finished_policy::*;
+pub use close_recipient_spending_cap::*;
+pub use close_user_payment::*;
+pub use close_user_payment_vault::*;
+pub use create_gateway_signers::*;
 pub use create_payment_gateway::*;
 pub use create_payment_policy::*;
+pub use create_recipient_settings::*;
+pub use create_recipient_spending_cap::*;
+pub use create_sol_user_payment::*;
 pub use create_user_payment::*;
+pub use create_user_payment_vault::*;
 pub use delete_payment_gateway::*;
 pub use delete_payment_policy::*;
+pub use deposit_sol::*;
+pub use deposit_to_vault::*;
+pub use deregister_gateway::*;
+pub use execute_config_change::*;
 pub use execute_payment::*;
+pub use execute_sol_payment::*;
+pub use extend_policy_term::*;
 pub use initialize::*;
+pub use lift_compliance_suspension::*;
+pub use migrate_gateway_policies::*;
+pub use migrate_policy_gateway::*;
+pub use open_dispute::*;
+pub use pause_payment_policy_until::*;
+pub use queue_config_change::*;
+pub use recipient_set_policy_status::*;
+pub use refund_payment::*;
+pub use refund_sol_payment::*;
+pub use register_gateway::*;
+pub use register_merchant::*;
+pub use release_escrow::*;
+pub use remove_from_denylist::*;
+pub use remove_gateway_signer::*;
+pub use remove_merchant_settlement_account::*;
+pub use report_payment_failure::*;
+pub use resolve_dispute::*;
+pub use revoke_merchant_verification::*;
+pub use set_close_policy_bounty::*;
+pub use set_config_role::*;
+pub use set_emergency_pause::*;
+pub use set_gateway_bond_config::*;
+pub use set_gateway_keeper_settings::*;
+pub use set_merchant_settlement_account::*;
+pub use set_policy_hold_period::*;
+pub use set_policy_max_pause::*;
+pub use set_recipient_require_acceptance::*;
+pub use set_spending_limit::*;
+pub use set_user_payment_active::*;
+pub use slash_gateway_bond::*;
+pub use stop_stream::*;
+pub use submit_usage_report::*;
+pub use suspend_denylisted_policies::*;
+pub use top_up_gateway_bond::*;
+pub use update_merchant::*;
+pub use update_user_payment_token_account::*;
+pub use verify_merchant::*;
+pub use withdraw_from_vault::*;
+pub use withdraw_gateway_bond::*;
+pub use withdraw_sol::*;
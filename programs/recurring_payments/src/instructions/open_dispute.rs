@@ -0,0 +1,62 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Box<Account<'info, UserPayment>>,
+
+    #[account(
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+        constraint = payment_policy.user_payment == user_payment.key() @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub payment_policy: Box<Account<'info, PaymentPolicy>>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, payment_policy.key().as_ref(), escrow.record_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.status == EscrowStatus::Held @ RecurringPaymentsError::InvalidEscrowStatus,
+    )]
+    pub escrow: Account<'info, PaymentEscrow>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn handler_open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp < escrow.release_at,
+        RecurringPaymentsError::HoldPeriodEnded
+    );
+
+    escrow.status = EscrowStatus::Disputed;
+
+    emit!(EscrowDisputed {
+        payment_policy: escrow.payment_policy,
+        escrow: escrow.key(),
+        record_id: escrow.record_id,
+    });
+
+    msg!(
+        "Dispute opened for record {} of payment policy {:?}",
+        escrow.record_id,
+        escrow.payment_policy
+    );
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+/// Vacation hold: pauses a policy until a given time. The periods in between
+/// are skipped and the policy resumes on its own at the next due date after it.
+#[derive(Accounts)]
+pub struct PausePaymentPolicyUntil<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, user_payment.key().as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+        constraint = payment_policy.status == PaymentStatus::Active @ RecurringPaymentsError::PolicyPaused,
+    )]
+    pub payment_policy: Account<'info, PaymentPolicy>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_pause_payment_policy_until(
+    ctx: Context<PausePaymentPolicyUntil>,
+    until: i64,
+) -> Result<()> {
+    let payment_policy = &mut ctx.accounts.payment_policy;
+    let clock = Clock::get()?;
+
+    require!(
+        until > clock.unix_timestamp,
+        RecurringPaymentsError::InvalidInterval
+    );
+    if payment_policy.max_pause_seconds > 0 {
+        require!(
+            until - clock.unix_timestamp <= payment_policy.max_pause_seconds as i64,
+            RecurringPaymentsError::PauseTooLong
+        );
+    }
+
+    // Make sure the periods can be skipped before pausing
+    payment_policy
+        .policy_type
+        .clone()
+        .skip_periods_until(until)?;
+
+    payment_policy.status = PaymentStatus::Paused;
+    payment_policy.paused_until = until;
+    payment_policy.updated_at = clock.unix_timestamp;
+
+    emit!(PaymentPolicyPausedUntil {
+        payment_policy: payment_policy.key(),
+        paused_until: until,
+    });
+
+    msg!(
+        "Payment policy ID: {} paused until {}",
+        payment_policy.policy_id,
+        until
+    );
+
+    Ok(())
+}
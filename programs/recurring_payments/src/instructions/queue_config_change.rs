@@ -0,0 +1,61 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(change: ConfigChange)]
+pub struct QueueConfigChange<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&authority.key(), change.required_role()) @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PendingConfigChange::SIZE,
+        seeds = [PENDING_CONFIG_CHANGE_SEED, &[change.kind()]],
+        bump
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_queue_config_change(
+    ctx: Context<QueueConfigChange>,
+    change: ConfigChange,
+) -> Result<()> {
+    change.validate()?;
+
+    let pending_config_change = &mut ctx.accounts.pending_config_change;
+    let clock = Clock::get()?;
+    let executable_at = clock
+        .unix_timestamp
+        .checked_add(ctx.accounts.config.timelock())
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+
+    pending_config_change.change = change.clone();
+    pending_config_change.queued_by = ctx.accounts.authority.key();
+    pending_config_change.queued_at = clock.unix_timestamp;
+    pending_config_change.executable_at = executable_at;
+    pending_config_change.bump = ctx.bumps.pending_config_change;
+
+    emit!(ConfigChangeQueued {
+        change: change.clone(),
+        queued_by: pending_config_change.queued_by,
+        executable_at,
+    });
+
+    msg!(
+        "Config change {:?} queued, executable at: {}",
+        change,
+        executable_at
+    );
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RecipientSetPolicyStatus<'info> {
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+        constraint = payment_policy.recipient == recipient.key() @ RecurringPaymentsError::Unauthorized,
+        constraint = payment_policy.status != PaymentStatus::Cancelled @ RecurringPaymentsError::PolicyCancelled,
+        constraint = payment_policy.status != PaymentStatus::ComplianceSuspended @ RecurringPaymentsError::PolicyComplianceSuspended,
+    )]
+    pub payment_policy: Account<'info, PaymentPolicy>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Lets the recipient stop pulling payments. `Suspended` blocks the policy
+/// until the recipient lifts it, which returns it to the payer as `Paused`.
+/// `Cancelled` is terminal.
+pub fn handler_recipient_set_policy_status(
+    ctx: Context<RecipientSetPolicyStatus>,
+    new_status: PaymentStatus,
+    reason_code: u16,
+) -> Result<()> {
+    let payment_policy = &mut ctx.accounts.payment_policy;
+    let clock = Clock::get()?;
+
+    let old_status = payment_policy.status.clone();
+    let is_allowed = match new_status {
+        PaymentStatus::Cancelled => true,
+        PaymentStatus::Suspended => old_status != PaymentStatus::Suspended,
+        PaymentStatus::Paused => old_status == PaymentStatus::Suspended,
+        PaymentStatus::Active
+        | PaymentStatus::PendingAcceptance
+        | PaymentStatus::ComplianceSuspended => false,
+    };
+    require!(is_allowed, RecurringPaymentsError::InvalidStatusTransition);
+
+    payment_policy.status = new_status.clone();
+    payment_policy.paused_until = 0;
+    payment_policy.updated_at = clock.unix_timestamp;
+
+    emit!(RecipientPolicyStatusChanged {
+        payment_policy: payment_policy.key(),
+        recipient: payment_policy.recipient,
+        old_status: old_status.clone(),
+        new_status,
+        reason_code,
+    });
+
+    msg!(
+        "Recipient changed payment policy status from {:?} to {:?} for policy ID: {} with reason code: {}",
+        old_status,
+        payment_policy.status,
+        payment_policy.policy_id,
+        reason_code
+    );
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::{
+    constants::*,
+    error::RecurringPaymentsError,
+    state::*,
+    utils::{is_refunder, stays_rent_exempt},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+};
+
+/// Refunds part of a native SOL payment. The recipient or an operator key of
+/// the policy's gateway sends the lamports to the payer's wallet.
+#[derive(Accounts)]
+pub struct RefundSolPayment<'info> {
+    #[account(mut)]
+    pub refunder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+    )]
+    pub payment_policy: Box<Account<'info, PaymentPolicy>>,
+
+    /// Required when a gateway key issues the refund
+    #[account(address = payment_policy.gateway @ RecurringPaymentsError::InvalidGateway)]
+    pub gateway: Option<Box<Account<'info, PaymentGateway>>>,
+
+    #[account(
+        seeds = [GATEWAY_SIGNERS_SEED, payment_policy.gateway.as_ref()],
+        bump = gateway_signers.bump,
+    )]
+    pub gateway_signers: Option<Box<Account<'info, GatewaySigners>>>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, user_payment.owner.as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.key() == payment_policy.user_payment,
+        constraint = user_payment.is_native_sol() @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub user_payment: Box<Account<'info, UserPayment>>,
+
+    /// CHECK: The payer's wallet, receives the lamports
+    #[account(mut, address = user_payment.owner)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_refund_sol_payment(
+    ctx: Context<RefundSolPayment>,
+    record_id: u32,
+    amount: u64,
+) -> Result<()> {
+    let refunder = ctx.accounts.refunder.key();
+    let clock = Clock::get()?;
+
+    require!(
+        is_refunder(
+            &refunder,
+            &ctx.accounts.payment_policy,
+            ctx.accounts.gateway.as_deref().map(|gateway| &**gateway),
+            ctx.accounts
+                .gateway_signers
+                .as_deref()
+                .map(|signers| &**signers),
+            clock.unix_timestamp,
+        ),
+        RecurringPaymentsError::Unauthorized
+    );
+
+    let payment_policy = &mut ctx.accounts.payment_policy;
+    require!(amount > 0, RecurringPaymentsError::InvalidAmount);
+    require!(
+        record_id > 0 && record_id <= payment_policy.payment_count,
+        RecurringPaymentsError::InvalidRecordId
+    );
+    payment_policy.book_refund(record_id, amount)?;
+    require!(
+        stays_rent_exempt(&ctx.accounts.owner.to_account_info(), amount, &Rent::get()?),
+        RecurringPaymentsError::DestinationNotRentExempt
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.refunder.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    payment_policy.updated_at = clock.unix_timestamp;
+
+    emit!(PaymentRefunded {
+        payment_policy: payment_policy.key(),
+        recipient: payment_policy.recipient,
+        refunded_by: refunder,
+        record_id,
+        amount,
+        total_refunded: payment_policy.total_refunded,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Refunded {} lamports for record {} of policy ID: {}",
+        amount,
+        record_id,
+        payment_policy.policy_id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct RegisterGateway<'info> {
+    /// The applicant that will own the gateway. Pays the rent and posts the bond.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PaymentGateway::SIZE,
+        seeds = [GATEWAY_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub gateway: Box<Account<'info, PaymentGateway>>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.gateway_bond_mint != Pubkey::default() @ RecurringPaymentsError::GatewayRegistrationDisabled,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+
+    /// CHECK: This is the fee recipient account that will receive gateway fees
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    #[account(address = config.gateway_bond_mint)]
+    pub bond_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [GATEWAY_BOND_SEED, gateway.key().as_ref()],
+        bump,
+        token::mint = bond_mint,
+        token::authority = gateway,
+    )]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key(),
+        constraint = authority_token_account.mint == bond_mint.key(),
+    )]
+    pub authority_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_register_gateway(
+    ctx: Context<RegisterGateway>,
+    gateway_fee_bps: u16,
+    name: [u8; 32],
+    url: [u8; 64],
+) -> Result<()> {
+    // Validate fee basis points
+    require!(
+        gateway_fee_bps <= 10000,
+        RecurringPaymentsError::InvalidFeeBps
+    );
+
+    let bond_amount = ctx.accounts.config.gateway_bond_amount;
+
+    // Post the bond
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.authority_token_account.to_account_info(),
+        to: ctx.accounts.bond_vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, bond_amount)?;
+
+    let config = &mut ctx.accounts.config;
+    config.bonded_gateways = config
+        .bonded_gateways
+        .checked_add(1)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+
+    let gateway = &mut ctx.accounts.gateway;
+    let clock = Clock::get()?;
+
+    gateway.authority = ctx.accounts.authority.key();
+    gateway.fee_recipient = ctx.accounts.fee_recipient.key();
+    gateway.gateway_fee_bps = gateway_fee_bps;
+    // Registered gateways stay inactive until the admin approves them
+    gateway.is_active = false;
+    gateway.total_processed = 0;
+    gateway.created_at = clock.unix_timestamp;
+    gateway.bump = ctx.bumps.gateway;
+    gateway.name = name;
+    gateway.url = url;
+    gateway.signer = ctx.accounts.authority.key();
+    gateway.active_policies_count = 0;
+    gateway.policy_count_complete = true;
+    gateway.bond_mint = ctx.accounts.bond_mint.key();
+    gateway.bond_amount = bond_amount;
+    gateway.bond_unlocks_at = 0;
+    gateway.keeper_grace_seconds = 0;
+    gateway.keeper_bounty_bps = 0;
+
+    emit!(GatewayRegistered {
+        gateway: gateway.key(),
+        authority: gateway.authority,
+        fee_recipient: gateway.fee_recipient,
+        gateway_fee_bps: gateway.gateway_fee_bps,
+        bond_mint: gateway.bond_mint,
+        bond_amount: gateway.bond_amount,
+        name: gateway.name,
+        url: gateway.url,
+    });
+
+    msg!(
+        "Payment gateway registered with authority: {:?}, bond: {}, name: {:?}",
+        gateway.authority,
+        gateway.bond_amount,
+        String::from_utf8_lossy(&name)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RegisterMerchant<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Merchant::SIZE,
+        seeds = [MERCHANT_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub merchant: Account<'info, Merchant>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_register_merchant(
+    ctx: Context<RegisterMerchant>,
+    name: [u8; 32],
+    url: [u8; 64],
+    logo_hash: [u8; 32],
+    support_contact: [u8; 64],
+) -> Result<()> {
+    let merchant = &mut ctx.accounts.merchant;
+    let clock = Clock::get()?;
+
+    merchant.authority = ctx.accounts.authority.key();
+    merchant.name = name;
+    merchant.url = url;
+    merchant.logo_hash = logo_hash;
+    merchant.support_contact = support_contact;
+    merchant.settlement_accounts = Vec::new();
+    merchant.verified_by = None;
+    merchant.verified_at = 0;
+    merchant.created_at = clock.unix_timestamp;
+    merchant.updated_at = clock.unix_timestamp;
+    merchant.bump = ctx.bumps.merchant;
+
+    emit!(MerchantUpdated {
+        merchant: merchant.key(),
+        authority: merchant.authority,
+        name,
+        url,
+        logo_hash,
+        support_contact,
+    });
+
+    msg!(
+        "Merchant registered for authority: {:?}, name: {:?}",
+        merchant.authority,
+        String::from_utf8_lossy(&name)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+
+/// Pay out the escrow vault to `destination` and close the vault. The escrow
+/// account itself is closed by the calling instruction.
+pub(crate) fn settle_escrow<'info>(
+    escrow: &Account<'info, PaymentEscrow>,
+    escrow_vault: &Account<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    rent_payer: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let record_id = escrow.record_id.to_le_bytes();
+    let seeds = &[
+        ESCROW_SEED,
+        escrow.payment_policy.as_ref(),
+        record_id.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if escrow_vault.amount > 0 {
+        let cpi_accounts = Transfer {
+            from: escrow_vault.to_account_info(),
+            to: destination,
+            authority: escrow.to_account_info(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, escrow_vault.amount)?;
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: escrow_vault.to_account_info(),
+        destination: rent_payer,
+        authority: escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+    token::close_account(cpi_ctx)
+}
+
+#[derive(Accounts)]
+pub struct ReleaseEscrow<'info> {
+    /// Anyone may release an escrow once its hold period has ended
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, escrow.payment_policy.as_ref(), escrow.record_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.status == EscrowStatus::Held @ RecurringPaymentsError::InvalidEscrowStatus,
+        close = rent_payer
+    )]
+    pub escrow: Account<'info, PaymentEscrow>,
+
+    #[account(
+        mut,
+        address = escrow.payment_policy @ RecurringPaymentsError::PolicyNotFound,
+    )]
+    pub payment_policy: Account<'info, PaymentPolicy>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_VAULT_SEED, escrow.payment_policy.as_ref(), escrow.record_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == escrow.token_mint,
+        constraint = recipient_token_account.owner == escrow.recipient,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Receives the rent of the escrow accounts
+    #[account(mut, address = escrow.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= escrow.release_at,
+        RecurringPaymentsError::HoldPeriodActive
+    );
+
+    let amount = ctx.accounts.escrow_vault.amount;
+    settle_escrow(
+        escrow,
+        &ctx.accounts.escrow_vault,
+        ctx.accounts.recipient_token_account.to_account_info(),
+        ctx.accounts.rent_payer.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    let payment_policy = &mut ctx.accounts.payment_policy;
+    payment_policy.open_escrows = payment_policy.open_escrows.saturating_sub(1);
+    payment_policy.updated_at = clock.unix_timestamp;
+
+    emit!(EscrowSettled {
+        payment_policy: escrow.payment_policy,
+        escrow: escrow.key(),
+        record_id: escrow.record_id,
+        amount,
+        to_recipient: true,
+    });
+
+    msg!(
+        "Released {} tokens from escrow for record {} of payment policy {:?}",
+        amount,
+        escrow.record_id,
+        escrow.payment_policy
+    );
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveFromDenylist<'info> {
+    #[account(mut)]
+    pub compliance: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&compliance.key(), ConfigRole::Compliance) @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        close = compliance,
+        seeds = [DENYLIST_SEED, denylist_entry.address.as_ref()],
+        bump = denylist_entry.bump,
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+}
+
+pub fn handler_remove_from_denylist(ctx: Context<RemoveFromDenylist>) -> Result<()> {
+    let address = ctx.accounts.denylist_entry.address;
+
+    emit!(AddressRemovedFromDenylist { address });
+
+    msg!("Address {:?} removed from the denylist", address);
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveGatewaySigner<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GATEWAY_SEED, authority.key().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.authority == authority.key()
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SIGNERS_SEED, gateway.key().as_ref()],
+        bump = gateway_signers.bump,
+    )]
+    pub gateway_signers: Account<'info, GatewaySigners>,
+}
+
+pub fn handler_remove_gateway_signer(
+    ctx: Context<RemoveGatewaySigner>,
+    signer: Pubkey,
+) -> Result<()> {
+    let gateway_signers = &mut ctx.accounts.gateway_signers;
+
+    let position = gateway_signers
+        .signers
+        .iter()
+        .position(|entry| entry.signer == signer)
+        .ok_or(RecurringPaymentsError::SignerNotFound)?;
+    gateway_signers.signers.remove(position);
+
+    emit!(GatewaySignerRemoved {
+        gateway: gateway_signers.gateway,
+        signer,
+    });
+
+    msg!(
+        "Gateway signer {:?} removed from gateway: {:?}",
+        signer,
+        gateway_signers.gateway
+    );
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveMerchantSettlementAccount<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MERCHANT_SEED, authority.key().as_ref()],
+        bump = merchant.bump,
+        constraint = merchant.authority == authority.key() @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub merchant: Account<'info, Merchant>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_remove_merchant_settlement_account(
+    ctx: Context<RemoveMerchantSettlementAccount>,
+    mint: Pubkey,
+) -> Result<()> {
+    let merchant = &mut ctx.accounts.merchant;
+    let clock = Clock::get()?;
+
+    let position = merchant
+        .settlement_accounts
+        .iter()
+        .position(|entry| entry.mint == mint)
+        .ok_or(RecurringPaymentsError::SettlementAccountNotFound)?;
+    merchant.settlement_accounts.remove(position);
+    merchant.updated_at = clock.unix_timestamp;
+
+    emit!(MerchantSettlementAccountChanged {
+        merchant: merchant.key(),
+        mint,
+        token_account: None,
+    });
+
+    msg!("Merchant settlement account for mint {:?} removed", mint);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*, utils::is_gateway_signer};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ReportPaymentFailure<'info> {
+    pub reporter: Signer<'info>,
+
+    #[account(
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+    )]
+    pub payment_policy: Box<Account<'info, PaymentPolicy>>,
+
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.authority.as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.key() == payment_policy.gateway @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub gateway: Box<Account<'info, PaymentGateway>>,
+
+    #[account(
+        seeds = [GATEWAY_SIGNERS_SEED, gateway.key().as_ref()],
+        bump = gateway_signers.bump,
+    )]
+    pub gateway_signers: Option<Box<Account<'info, GatewaySigners>>>,
+}
+
+pub fn handler_report_payment_failure(
+    ctx: Context<ReportPaymentFailure>,
+    reason_code: u16,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let reporter = ctx.accounts.reporter.key();
+
+    require!(
+        is_gateway_signer(
+            &ctx.accounts.gateway,
+            ctx.accounts
+                .gateway_signers
+                .as_deref()
+                .map(|signers| &**signers),
+            &reporter,
+            GatewaySignerRole::Operator,
+            clock.unix_timestamp,
+        ),
+        RecurringPaymentsError::Unauthorized
+    );
+
+    emit!(PaymentFailed {
+        payment_policy: ctx.accounts.payment_policy.key(),
+        gateway: ctx.accounts.gateway.key(),
+        reporter,
+        reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Payment failure reported for policy ID: {} with reason code: {}",
+        ctx.accounts.payment_policy.policy_id,
+        reason_code
+    );
+
+    Ok(())
+}
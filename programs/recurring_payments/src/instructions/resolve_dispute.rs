@@ -0,0 +1,150 @@
+use crate::{
+    constants::*, error::RecurringPaymentsError, instructions::release_escrow::settle_escrow,
+    state::*, utils::is_gateway_signer,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    /// The gateway acts as arbiter
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+    )]
+    pub payment_policy: Box<Account<'info, PaymentPolicy>>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, user_payment.owner.as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.key() == payment_policy.user_payment,
+    )]
+    pub user_payment: Box<Account<'info, UserPayment>>,
+
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.authority.as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.key() == payment_policy.gateway @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub gateway: Box<Account<'info, PaymentGateway>>,
+
+    #[account(
+        seeds = [GATEWAY_SIGNERS_SEED, gateway.key().as_ref()],
+        bump = gateway_signers.bump,
+    )]
+    pub gateway_signers: Option<Box<Account<'info, GatewaySigners>>>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, payment_policy.key().as_ref(), escrow.record_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.status == EscrowStatus::Disputed @ RecurringPaymentsError::InvalidEscrowStatus,
+        close = rent_payer
+    )]
+    pub escrow: Box<Account<'info, PaymentEscrow>>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_VAULT_SEED, payment_policy.key().as_ref(), escrow.record_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow_vault: Box<Account<'info, TokenAccount>>,
+
+    /// The recipient's token account if the dispute is decided for the recipient,
+    /// otherwise the payer's token account
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == escrow.token_mint,
+    )]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives the rent of the escrow accounts
+    #[account(mut, address = escrow.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_resolve_dispute(
+    ctx: Context<ResolveDispute>,
+    release_to_recipient: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        is_gateway_signer(
+            &ctx.accounts.gateway,
+            ctx.accounts
+                .gateway_signers
+                .as_deref()
+                .map(|signers| &**signers),
+            &ctx.accounts.arbiter.key(),
+            GatewaySignerRole::Operator,
+            clock.unix_timestamp,
+        ),
+        RecurringPaymentsError::Unauthorized
+    );
+
+    let escrow = &ctx.accounts.escrow;
+    let destination = &ctx.accounts.destination_token_account;
+    if release_to_recipient {
+        require!(
+            destination.owner == escrow.recipient,
+            RecurringPaymentsError::Unauthorized
+        );
+    } else {
+        require!(
+            destination.key() == ctx.accounts.user_payment.token_account,
+            RecurringPaymentsError::Unauthorized
+        );
+    }
+
+    let amount = ctx.accounts.escrow_vault.amount;
+    settle_escrow(
+        escrow,
+        &ctx.accounts.escrow_vault,
+        destination.to_account_info(),
+        ctx.accounts.rent_payer.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    let payment_policy = &mut ctx.accounts.payment_policy;
+    payment_policy.open_escrows = payment_policy.open_escrows.saturating_sub(1);
+    payment_policy.updated_at = clock.unix_timestamp;
+
+    // Returning the escrowed share to the payer counts as a refund
+    if !release_to_recipient {
+        payment_policy.book_dispute_refund(escrow.record_id, amount)?;
+    }
+
+    emit!(EscrowSettled {
+        payment_policy: escrow.payment_policy,
+        escrow: escrow.key(),
+        record_id: escrow.record_id,
+        amount,
+        to_recipient: release_to_recipient,
+    });
+
+    msg!(
+        "Dispute for record {} resolved, {} tokens sent to {}",
+        escrow.record_id,
+        amount,
+        if release_to_recipient {
+            "recipient"
+        } else {
+            "payer"
+        }
+    );
+
+    Ok(())
+}
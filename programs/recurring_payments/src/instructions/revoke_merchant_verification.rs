@@ -0,0 +1,46 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RevokeMerchantVerification<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GATEWAY_SEED, authority.key().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.authority == authority.key() @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        mut,
+        seeds = [MERCHANT_SEED, merchant.authority.as_ref()],
+        bump = merchant.bump,
+        constraint = merchant.verified_by == Some(gateway.key()) @ RecurringPaymentsError::MerchantNotVerifiedByGateway,
+    )]
+    pub merchant: Account<'info, Merchant>,
+}
+
+pub fn handler_revoke_merchant_verification(
+    ctx: Context<RevokeMerchantVerification>,
+) -> Result<()> {
+    let merchant = &mut ctx.accounts.merchant;
+    let gateway = ctx.accounts.gateway.key();
+
+    merchant.verified_by = None;
+    merchant.verified_at = 0;
+
+    emit!(MerchantVerificationChanged {
+        merchant: merchant.key(),
+        gateway,
+        verified: false,
+    });
+
+    msg!(
+        "Merchant {:?} verification revoked by gateway: {:?}",
+        merchant.authority,
+        gateway
+    );
+
+    Ok(())
+}
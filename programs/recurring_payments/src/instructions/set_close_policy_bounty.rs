@@ -0,0 +1,34 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetClosePolicyBounty<'info> {
+    pub fee_manager: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&fee_manager.key(), ConfigRole::FeeManager) @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_set_close_policy_bounty(
+    ctx: Context<SetClosePolicyBounty>,
+    bounty_lamports: u64,
+) -> Result<()> {
+    require!(
+        bounty_lamports <= MAX_CLOSE_POLICY_BOUNTY_LAMPORTS,
+        RecurringPaymentsError::InvalidAmount
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.close_policy_bounty_lamports = bounty_lamports;
+
+    emit!(ClosePolicyBountyUpdated { bounty_lamports });
+
+    msg!("Close policy bounty set to {} lamports", bounty_lamports);
+
+    Ok(())
+}
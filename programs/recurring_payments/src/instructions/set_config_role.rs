@@ -0,0 +1,51 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetConfigRole<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&admin.key(), ConfigRole::Admin) @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Assign `role` to `holder`. Assigning the default key hands the role back
+/// to the admin, the admin role itself must always have a holder.
+pub fn handler_set_config_role(
+    ctx: Context<SetConfigRole>,
+    role: ConfigRole,
+    holder: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_holder = config.role_holder(role);
+
+    match role {
+        ConfigRole::Admin => {
+            require!(
+                holder != Pubkey::default(),
+                RecurringPaymentsError::Unauthorized
+            );
+            config.admin = holder;
+        }
+        ConfigRole::Pauser => config.pauser = holder,
+        ConfigRole::FeeManager => config.fee_manager = holder,
+        ConfigRole::GatewayApprover => config.gateway_approver = holder,
+        ConfigRole::Compliance => config.compliance = holder,
+    }
+
+    let new_holder = config.role_holder(role);
+    emit!(ConfigRoleChanged {
+        role,
+        old_holder,
+        new_holder,
+    });
+
+    msg!("Config role {:?} assigned to: {:?}", role, new_holder);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetEmergencyPause<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_set_emergency_pause(ctx: Context<SetEmergencyPause>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let authority = ctx.accounts.authority.key();
+
+    // The pauser is a hot key, only the admin may lift a pause
+    let role = if paused {
+        ConfigRole::Pauser
+    } else {
+        ConfigRole::Admin
+    };
+    require!(
+        config.has_role(&authority, role),
+        RecurringPaymentsError::Unauthorized
+    );
+
+    config.emergency_pause = paused;
+
+    emit!(EmergencyPauseChanged {
+        paused,
+        changed_by: authority,
+    });
+
+    msg!("Emergency pause set to {} by: {:?}", paused, authority);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+#[derive(Accounts)]
+pub struct SetGatewayBondConfig<'info> {
+    pub fee_manager: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&fee_manager.key(), ConfigRole::FeeManager) @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub bond_mint: Account<'info, Mint>,
+}
+
+pub fn handler_set_gateway_bond_config(
+    ctx: Context<SetGatewayBondConfig>,
+    bond_amount: u64,
+    cooldown_seconds: i64,
+) -> Result<()> {
+    require!(bond_amount > 0, RecurringPaymentsError::InvalidAmount);
+    require!(
+        cooldown_seconds >= 0,
+        RecurringPaymentsError::InvalidInterval
+    );
+
+    let config = &mut ctx.accounts.config;
+
+    // Posted bonds are only comparable in the mint they were posted in
+    require!(
+        config.gateway_bond_mint == ctx.accounts.bond_mint.key() || config.bonded_gateways == 0,
+        RecurringPaymentsError::GatewayBondsOutstanding
+    );
+
+    config.gateway_bond_mint = ctx.accounts.bond_mint.key();
+    config.gateway_bond_amount = bond_amount;
+    config.gateway_bond_cooldown_seconds = cooldown_seconds;
+
+    emit!(GatewayBondConfigUpdated {
+        bond_mint: config.gateway_bond_mint,
+        bond_amount: config.gateway_bond_amount,
+        cooldown_seconds: config.gateway_bond_cooldown_seconds,
+    });
+
+    msg!(
+        "Gateway bond set to {} of mint {:?} with {}s cooldown",
+        config.gateway_bond_amount,
+        config.gateway_bond_mint,
+        config.gateway_bond_cooldown_seconds
+    );
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetGatewayKeeperSettings<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, authority.key().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.authority == authority.key()
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_set_gateway_keeper_settings(
+    ctx: Context<SetGatewayKeeperSettings>,
+    keeper_grace_seconds: i64,
+    keeper_bounty_bps: u16,
+) -> Result<()> {
+    require!(
+        keeper_grace_seconds >= 0,
+        RecurringPaymentsError::InvalidInterval
+    );
+    require!(
+        keeper_bounty_bps <= 10000,
+        RecurringPaymentsError::InvalidFeeBps
+    );
+
+    let gateway = &mut ctx.accounts.gateway;
+
+    gateway.keeper_grace_seconds = keeper_grace_seconds;
+    gateway.keeper_bounty_bps = keeper_bounty_bps;
+
+    emit!(GatewayKeeperSettingsChanged {
+        gateway: gateway.key(),
+        keeper_grace_seconds,
+        keeper_bounty_bps,
+    });
+
+    msg!(
+        "Gateway keeper settings changed to {}s grace and {} bps bounty for gateway: {:?}",
+        keeper_grace_seconds,
+        keeper_bounty_bps,
+        gateway.key()
+    );
+
+    Ok(())
+}
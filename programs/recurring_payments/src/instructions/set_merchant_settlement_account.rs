@@ -0,0 +1,73 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct SetMerchantSettlementAccount<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MERCHANT_SEED, authority.key().as_ref()],
+        bump = merchant.bump,
+        constraint = merchant.authority == authority.key() @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub merchant: Account<'info, Merchant>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    /// Payments are only made to token accounts owned by the recipient
+    #[account(
+        constraint = settlement_token_account.mint == token_mint.key(),
+        constraint = settlement_token_account.owner == authority.key() @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub settlement_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_set_merchant_settlement_account(
+    ctx: Context<SetMerchantSettlementAccount>,
+) -> Result<()> {
+    let merchant = &mut ctx.accounts.merchant;
+    let clock = Clock::get()?;
+
+    let entry = SettlementAccount {
+        mint: ctx.accounts.token_mint.key(),
+        token_account: ctx.accounts.settlement_token_account.key(),
+    };
+    match merchant
+        .settlement_accounts
+        .iter_mut()
+        .find(|existing| existing.mint == entry.mint)
+    {
+        Some(existing) => *existing = entry.clone(),
+        None => {
+            require!(
+                merchant.settlement_accounts.len() < Merchant::MAX_SETTLEMENT_ACCOUNTS,
+                RecurringPaymentsError::MaxSettlementAccountsReached
+            );
+            merchant.settlement_accounts.push(entry.clone());
+        }
+    }
+    merchant.updated_at = clock.unix_timestamp;
+
+    emit!(MerchantSettlementAccountChanged {
+        merchant: merchant.key(),
+        mint: entry.mint,
+        token_account: Some(entry.token_account),
+    });
+
+    msg!(
+        "Merchant settlement account for mint {:?} set to {:?}",
+        entry.mint,
+        entry.token_account
+    );
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+/// Lets the recipient hold its payouts in escrow for longer. The hold period
+/// the payer agreed to can only be raised, so the payer's dispute window
+/// never shrinks.
+#[derive(Accounts)]
+pub struct SetPolicyHoldPeriod<'info> {
+    /// Only the recipient may delay its own payouts
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+        constraint = payment_policy.recipient == recipient.key() @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub payment_policy: Account<'info, PaymentPolicy>,
+
+    #[account(
+        constraint = user_payment.key() == payment_policy.user_payment,
+        constraint = !user_payment.is_native_sol() @ RecurringPaymentsError::NotSupportedForSol,
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_set_policy_hold_period(
+    ctx: Context<SetPolicyHoldPeriod>,
+    hold_seconds: u32,
+) -> Result<()> {
+    let payment_policy = &mut ctx.accounts.payment_policy;
+    let clock = Clock::get()?;
+
+    require!(
+        hold_seconds >= payment_policy.hold_seconds && hold_seconds <= MAX_HOLD_SECONDS,
+        RecurringPaymentsError::InvalidHoldPeriod
+    );
+
+    payment_policy.hold_seconds = hold_seconds;
+    payment_policy.updated_at = clock.unix_timestamp;
+
+    emit!(PaymentPolicyHoldChanged {
+        payment_policy: payment_policy.key(),
+        hold_seconds,
+    });
+
+    msg!(
+        "Payment policy ID: {} hold period set to {}s",
+        payment_policy.policy_id,
+        hold_seconds
+    );
+
+    Ok(())
+}
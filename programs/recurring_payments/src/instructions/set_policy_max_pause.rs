@@ -0,0 +1,47 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPolicyMaxPause<'info> {
+    /// The recipient's plan decides how long payers may pause
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+        constraint = payment_policy.recipient == recipient.key() @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub payment_policy: Account<'info, PaymentPolicy>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_set_policy_max_pause(
+    ctx: Context<SetPolicyMaxPause>,
+    max_pause_seconds: u32,
+) -> Result<()> {
+    let payment_policy = &mut ctx.accounts.payment_policy;
+    let clock = Clock::get()?;
+
+    payment_policy.max_pause_seconds = max_pause_seconds;
+    payment_policy.updated_at = clock.unix_timestamp;
+
+    emit!(PaymentPolicyMaxPauseChanged {
+        payment_policy: payment_policy.key(),
+        max_pause_seconds,
+    });
+
+    msg!(
+        "Payment policy ID: {} max pause set to {}s",
+        payment_policy.policy_id,
+        max_pause_seconds
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRecipientRequireAcceptance<'info> {
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SETTINGS_SEED, recipient.key().as_ref()],
+        bump = recipient_settings.bump,
+    )]
+    pub recipient_settings: Account<'info, RecipientSettings>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_set_recipient_require_acceptance(
+    ctx: Context<SetRecipientRequireAcceptance>,
+    require_acceptance: bool,
+) -> Result<()> {
+    let recipient_settings = &mut ctx.accounts.recipient_settings;
+    let clock = Clock::get()?;
+
+    // Only affects policies created from now on
+    recipient_settings.require_acceptance = require_acceptance;
+    recipient_settings.updated_at = clock.unix_timestamp;
+
+    emit!(RecipientSettingsUpdated {
+        recipient: recipient_settings.recipient,
+        require_acceptance,
+    });
+
+    msg!(
+        "Recipient {:?} require acceptance set to {}",
+        recipient_settings.recipient,
+        require_acceptance
+    );
+
+    Ok(())
+}
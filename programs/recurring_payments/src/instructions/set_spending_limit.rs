@@ -0,0 +1,55 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetSpendingLimit<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_set_spending_limit(
+    ctx: Context<SetSpendingLimit>,
+    limit: u64,
+    period_seconds: i64,
+) -> Result<()> {
+    let user_payment = &mut ctx.accounts.user_payment;
+    let clock = Clock::get()?;
+
+    // Keep what was spent within the window when only the limit changes
+    let mut spending_limit = SpendingLimit::new(limit, period_seconds)?;
+    if user_payment.spending_limit.period_seconds == period_seconds {
+        spending_limit.latest_bucket = user_payment.spending_limit.latest_bucket;
+        spending_limit.buckets = user_payment.spending_limit.buckets;
+    }
+    user_payment.spending_limit = spending_limit;
+    user_payment.updated_at = clock.unix_timestamp;
+
+    emit!(SpendingLimitSet {
+        user_payment: user_payment.key(),
+        limit,
+        period_seconds,
+    });
+
+    msg!(
+        "Spending limit of user payment of: {:?} set to {} per {}s",
+        user_payment.owner,
+        limit,
+        period_seconds
+    );
+
+    Ok(())
+}
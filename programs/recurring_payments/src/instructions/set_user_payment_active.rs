@@ -0,0 +1,47 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetUserPaymentActive<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_set_user_payment_active(
+    ctx: Context<SetUserPaymentActive>,
+    is_active: bool,
+) -> Result<()> {
+    let user_payment = &mut ctx.accounts.user_payment;
+    let clock = Clock::get()?;
+
+    // An inactive user payment blocks the execution of all of its policies
+    user_payment.is_active = is_active;
+    user_payment.updated_at = clock.unix_timestamp;
+
+    emit!(UserPaymentActiveChanged {
+        user_payment: user_payment.key(),
+        is_active,
+    });
+
+    msg!(
+        "User payment of: {:?} set to active: {}",
+        user_payment.owner,
+        is_active
+    );
+
+    Ok(())
+}
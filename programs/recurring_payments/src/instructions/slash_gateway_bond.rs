@@ -0,0 +1,91 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct SlashGatewayBond<'info> {
+    pub gateway_approver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.authority.as_ref()],
+        bump = gateway.bump,
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_BOND_SEED, gateway.key().as_ref()],
+        bump,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&gateway_approver.key(), ConfigRole::GatewayApprover) @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        constraint = protocol_fee_account.mint == gateway.bond_mint,
+        constraint = protocol_fee_account.owner == config.fee_recipient,
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_slash_gateway_bond(ctx: Context<SlashGatewayBond>, amount: u64) -> Result<()> {
+    require!(
+        amount > 0 && amount <= ctx.accounts.gateway.bond_amount,
+        RecurringPaymentsError::InvalidAmount
+    );
+
+    // The gateway PDA owns the bond vault
+    let gateway = &ctx.accounts.gateway;
+    let seeds = &[GATEWAY_SEED, gateway.authority.as_ref(), &[gateway.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.bond_vault.to_account_info(),
+        to: ctx.accounts.protocol_fee_account.to_account_info(),
+        authority: gateway.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.bond_amount = gateway
+        .bond_amount
+        .checked_sub(amount)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+
+    // An undercollateralized gateway stops processing until it tops up its
+    // bond and is approved again
+    if gateway.bond_mint != ctx.accounts.config.gateway_bond_mint
+        || gateway.bond_amount < ctx.accounts.config.gateway_bond_amount
+    {
+        gateway.is_active = false;
+    }
+
+    emit!(GatewayBondSlashed {
+        gateway: gateway.key(),
+        amount,
+        remaining_bond: gateway.bond_amount,
+        is_active: gateway.is_active,
+    });
+
+    msg!(
+        "Slashed {} from bond of gateway {:?}, {} remaining",
+        amount,
+        gateway.key(),
+        gateway.bond_amount
+    );
+
+    Ok(())
+}
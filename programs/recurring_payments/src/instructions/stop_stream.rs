@@ -0,0 +1,64 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct StopStream<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Box<Account<'info, UserPayment>>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+        constraint = payment_policy.user_payment == user_payment.key() @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub payment_policy: Box<Account<'info, PaymentPolicy>>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn handler_stop_stream(ctx: Context<StopStream>) -> Result<()> {
+    let payment_policy = &mut ctx.accounts.payment_policy;
+    let clock = Clock::get()?;
+
+    // Cut the stream off at the current time. Whatever vested so far stays
+    // claimable by the recipient, the unvested remainder is never charged.
+    let end = match &mut payment_policy.policy_type {
+        PolicyType::Stream { start, end, .. } => {
+            if clock.unix_timestamp < *end {
+                *end = clock.unix_timestamp.max(*start);
+            }
+            *end
+        }
+        _ => return err!(RecurringPaymentsError::NotAStream),
+    };
+    let (claimable, _) = payment_policy
+        .policy_type
+        .due_payment(clock.unix_timestamp)?;
+    payment_policy.updated_at = clock.unix_timestamp;
+
+    emit!(StreamStopped {
+        payment_policy: payment_policy.key(),
+        end,
+        claimable,
+    });
+
+    msg!(
+        "Payment policy ID: {} stream stopped at {}",
+        payment_policy.policy_id,
+        end
+    );
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::{
+    constants::*,
+    error::RecurringPaymentsError,
+    state::*,
+    utils::{is_gateway_signer, resume_if_due},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SubmitUsageReport<'info> {
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_POLICY_SEED, payment_policy.user_payment.as_ref(), payment_policy.policy_id.to_le_bytes().as_ref()],
+        bump = payment_policy.bump,
+        constraint = payment_policy.status == PaymentStatus::Active || payment_policy.paused_until > 0 @ RecurringPaymentsError::PolicyPaused,
+    )]
+    pub payment_policy: Box<Account<'info, PaymentPolicy>>,
+
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.authority.as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.key() == payment_policy.gateway @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub gateway: Box<Account<'info, PaymentGateway>>,
+
+    #[account(
+        seeds = [GATEWAY_SIGNERS_SEED, gateway.key().as_ref()],
+        bump = gateway_signers.bump,
+    )]
+    pub gateway_signers: Option<Box<Account<'info, GatewaySigners>>>,
+
+    /// One report per period, charged by the payment with the same record id
+    #[account(
+        init,
+        payer = reporter,
+        space = UsageReport::SIZE,
+        seeds = [
+            USAGE_REPORT_SEED,
+            payment_policy.key().as_ref(),
+            (payment_policy.payment_count + 1).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub usage_report: Account<'info, UsageReport>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_submit_usage_report(ctx: Context<SubmitUsageReport>, units: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let reporter = ctx.accounts.reporter.key();
+
+    // A scheduled pause ends on its own
+    resume_if_due(&mut ctx.accounts.payment_policy, clock.unix_timestamp)?;
+    let payment_policy = &ctx.accounts.payment_policy;
+    require!(
+        payment_policy.status == PaymentStatus::Active,
+        RecurringPaymentsError::PolicyPaused
+    );
+
+    require!(
+        is_gateway_signer(
+            &ctx.accounts.gateway,
+            ctx.accounts
+                .gateway_signers
+                .as_deref()
+                .map(|signers| &**signers),
+            &reporter,
+            GatewaySignerRole::Executor,
+            clock.unix_timestamp,
+        ),
+        RecurringPaymentsError::Unauthorized
+    );
+
+    let (period_end, max_units_per_period) = match &payment_policy.policy_type {
+        PolicyType::Metered {
+            next_payment_due,
+            max_units_per_period,
+            ..
+        } => (*next_payment_due, *max_units_per_period),
+        _ => return err!(RecurringPaymentsError::NotMetered),
+    };
+
+    // Usage can only be reported once the period has closed
+    require!(
+        clock.unix_timestamp >= period_end,
+        RecurringPaymentsError::PaymentNotDue
+    );
+    require!(
+        units <= max_units_per_period,
+        RecurringPaymentsError::UsageExceedsCap
+    );
+
+    let usage_report = &mut ctx.accounts.usage_report;
+    usage_report.payment_policy = payment_policy.key();
+    usage_report.record_id = payment_policy.payment_count + 1;
+    usage_report.period_end = period_end;
+    usage_report.units = units;
+    usage_report.reporter = reporter;
+    usage_report.reported_at = clock.unix_timestamp;
+    usage_report.amount_charged = 0;
+    usage_report.charged_at = 0;
+    usage_report.bump = ctx.bumps.usage_report;
+
+    emit!(UsageReported {
+        payment_policy: usage_report.payment_policy,
+        usage_report: usage_report.key(),
+        record_id: usage_report.record_id,
+        period_end,
+        units,
+        reporter,
+    });
+
+    msg!(
+        "Usage of {} units reported for policy ID: {}",
+        units,
+        payment_policy.policy_id
+    );
+
+    Ok(())
+}
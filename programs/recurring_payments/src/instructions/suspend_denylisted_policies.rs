@@ -0,0 +1,88 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+/// Suspends the policies of a denylisted address in bulk. The policies are
+/// passed as writable remaining accounts and must either pay the address or
+/// be paid from `user_payment`, which must belong to it. Policies the
+/// recipient suspended are taken over, so only the Compliance role can lift
+/// them, and go back to the recipient's suspension once lifted. Cancelled and already suspended policies are skipped.
+#[derive(Accounts)]
+pub struct SuspendDenylistedPolicies<'info> {
+    pub compliance: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.has_role(&compliance.key(), ConfigRole::Compliance) @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [DENYLIST_SEED, denylist_entry.address.as_ref()],
+        bump = denylist_entry.bump,
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+
+    /// A user payment of the denylisted address, to suspend policies it pays
+    #[account(
+        seeds = [USER_PAYMENT_SEED, user_payment.owner.as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == denylist_entry.address @ RecurringPaymentsError::PolicyNotDenylisted,
+    )]
+    pub user_payment: Option<Account<'info, UserPayment>>,
+}
+
+pub fn handler_suspend_denylisted_policies(ctx: Context<SuspendDenylistedPolicies>) -> Result<()> {
+    let address = ctx.accounts.denylist_entry.address;
+    let user_payment = ctx
+        .accounts
+        .user_payment
+        .as_ref()
+        .map(|user_payment| user_payment.key());
+    let clock = Clock::get()?;
+    let mut suspended = 0u32;
+
+    for policy_info in ctx.remaining_accounts.iter() {
+        require!(
+            policy_info.owner == &crate::ID && policy_info.is_writable,
+            RecurringPaymentsError::PolicyNotFound
+        );
+
+        let mut data = policy_info.try_borrow_mut_data()?;
+        let mut payment_policy = PaymentPolicy::try_deserialize(&mut &data[..])?;
+        require!(
+            payment_policy.recipient == address
+                || Some(payment_policy.user_payment) == user_payment,
+            RecurringPaymentsError::PolicyNotDenylisted
+        );
+
+        let old_status = payment_policy.status.clone();
+        if matches!(
+            old_status,
+            PaymentStatus::Cancelled | PaymentStatus::ComplianceSuspended
+        ) {
+            continue;
+        }
+
+        payment_policy.suspended_by_recipient = old_status == PaymentStatus::Suspended;
+        payment_policy.status = PaymentStatus::ComplianceSuspended;
+        payment_policy.paused_until = 0;
+        payment_policy.updated_at = clock.unix_timestamp;
+        payment_policy.try_serialize(&mut &mut data[..])?;
+        suspended += 1;
+
+        emit!(PaymentPolicyStatusChanged {
+            payment_policy: policy_info.key(),
+            old_status,
+            new_status: PaymentStatus::ComplianceSuspended,
+        });
+    }
+
+    msg!(
+        "Suspended {} payment policies involving denylisted address {:?}",
+        suspended,
+        address
+    );
+
+    Ok(())
+}
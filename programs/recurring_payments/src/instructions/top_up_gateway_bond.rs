@@ -0,0 +1,68 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Adds to the bond of a registered gateway, e.g. after it was slashed
+#[derive(Accounts)]
+pub struct TopUpGatewayBond<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, authority.key().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.authority == authority.key(),
+        constraint = gateway.bond_mint != Pubkey::default() @ RecurringPaymentsError::InvalidGateway,
+        constraint = gateway.bond_unlocks_at == 0 @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_BOND_SEED, gateway.key().as_ref()],
+        bump,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key(),
+        constraint = authority_token_account.mint == gateway.bond_mint,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_top_up_gateway_bond(ctx: Context<TopUpGatewayBond>, amount: u64) -> Result<()> {
+    require!(amount > 0, RecurringPaymentsError::InvalidAmount);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.authority_token_account.to_account_info(),
+        to: ctx.accounts.bond_vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.bond_amount = gateway
+        .bond_amount
+        .checked_add(amount)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+
+    emit!(GatewayBondToppedUp {
+        gateway: gateway.key(),
+        amount,
+        bond_amount: gateway.bond_amount,
+    });
+
+    msg!(
+        "Added {} to bond of gateway {:?}, {} posted",
+        amount,
+        gateway.key(),
+        gateway.bond_amount
+    );
+
+    Ok(())
+}
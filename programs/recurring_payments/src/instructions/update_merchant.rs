@@ -0,0 +1,67 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateMerchant<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MERCHANT_SEED, authority.key().as_ref()],
+        bump = merchant.bump,
+        constraint = merchant.authority == authority.key() @ RecurringPaymentsError::Unauthorized,
+    )]
+    pub merchant: Account<'info, Merchant>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_update_merchant(
+    ctx: Context<UpdateMerchant>,
+    name: [u8; 32],
+    url: [u8; 64],
+    logo_hash: [u8; 32],
+    support_contact: [u8; 64],
+) -> Result<()> {
+    let merchant = &mut ctx.accounts.merchant;
+    let clock = Clock::get()?;
+
+    merchant.name = name;
+    merchant.url = url;
+    merchant.logo_hash = logo_hash;
+    merchant.support_contact = support_contact;
+    merchant.updated_at = clock.unix_timestamp;
+
+    // The attestation covered the old profile
+    if let Some(gateway) = merchant.verified_by.take() {
+        merchant.verified_at = 0;
+
+        emit!(MerchantVerificationChanged {
+            merchant: merchant.key(),
+            gateway,
+            verified: false,
+        });
+    }
+
+    emit!(MerchantUpdated {
+        merchant: merchant.key(),
+        authority: merchant.authority,
+        name,
+        url,
+        logo_hash,
+        support_contact,
+    });
+
+    msg!(
+        "Merchant updated for authority: {:?}, name: {:?}",
+        merchant.authority,
+        String::from_utf8_lossy(&name)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct UpdateUserPaymentTokenAccount<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+        constraint = !user_payment.is_native_sol() @ RecurringPaymentsError::NotSupportedForSol,
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        constraint = new_token_account.owner == owner.key(),
+        constraint = new_token_account.mint == user_payment.token_mint,
+    )]
+    pub new_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_update_user_payment_token_account(
+    ctx: Context<UpdateUserPaymentTokenAccount>,
+) -> Result<()> {
+    let user_payment = &mut ctx.accounts.user_payment;
+    let clock = Clock::get()?;
+
+    let old_token_account = user_payment.token_account;
+    user_payment.token_account = ctx.accounts.new_token_account.key();
+    user_payment.updated_at = clock.unix_timestamp;
+
+    emit!(UserPaymentTokenAccountChanged {
+        user_payment: user_payment.key(),
+        old_token_account,
+        new_token_account: user_payment.token_account,
+    });
+
+    msg!(
+        "Token account of user payment of: {:?} changed from {:?} to {:?}",
+        user_payment.owner,
+        old_token_account,
+        user_payment.token_account
+    );
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+
+/// A gateway attests to a merchant profile after checking it off-chain
+#[derive(Accounts)]
+pub struct VerifyMerchant<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GATEWAY_SEED, authority.key().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.authority == authority.key() @ RecurringPaymentsError::Unauthorized,
+        constraint = gateway.is_active @ RecurringPaymentsError::InvalidGateway,
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    #[account(
+        mut,
+        seeds = [MERCHANT_SEED, merchant.authority.as_ref()],
+        bump = merchant.bump,
+    )]
+    pub merchant: Account<'info, Merchant>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_pause @ RecurringPaymentsError::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn handler_verify_merchant(ctx: Context<VerifyMerchant>) -> Result<()> {
+    let merchant = &mut ctx.accounts.merchant;
+    let gateway = ctx.accounts.gateway.key();
+    let clock = Clock::get()?;
+
+    merchant.verified_by = Some(gateway);
+    merchant.verified_at = clock.unix_timestamp;
+
+    emit!(MerchantVerificationChanged {
+        merchant: merchant.key(),
+        gateway,
+        verified: true,
+    });
+
+    msg!(
+        "Merchant {:?} verified by gateway: {:?}",
+        merchant.authority,
+        gateway
+    );
+
+    Ok(())
+}
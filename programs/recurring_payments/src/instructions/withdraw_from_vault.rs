@@ -0,0 +1,75 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct WithdrawFromVault<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        mut,
+        address = user_payment.vault @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key(),
+        constraint = owner_token_account.mint == user_payment.token_mint,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_withdraw_from_vault(ctx: Context<WithdrawFromVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, RecurringPaymentsError::InvalidAmount);
+    require!(
+        ctx.accounts.vault.amount >= amount,
+        RecurringPaymentsError::InsufficientBalance
+    );
+
+    // The user payment account owns the vault
+    let user_payment = &ctx.accounts.user_payment;
+    let seeds = &[
+        USER_PAYMENT_SEED,
+        user_payment.owner.as_ref(),
+        user_payment.token_mint.as_ref(),
+        &[user_payment.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.owner_token_account.to_account_info(),
+        authority: user_payment.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    ctx.accounts.vault.reload()?;
+
+    emit!(VaultWithdrawn {
+        user_payment: ctx.accounts.user_payment.key(),
+        amount,
+        balance: ctx.accounts.vault.amount,
+    });
+
+    msg!(
+        "Withdrew {} tokens from prepaid vault, balance: {}",
+        amount,
+        ctx.accounts.vault.amount
+    );
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*, utils::close_gateway_signers};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct WithdrawGatewayBond<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, authority.key().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.authority == authority.key(),
+        constraint = gateway.bond_unlocks_at != 0 @ RecurringPaymentsError::BondLocked,
+        constraint = gateway.active_policies_count == 0 @ RecurringPaymentsError::GatewayHasActivePolicies,
+        close = authority
+    )]
+    pub gateway: Account<'info, PaymentGateway>,
+
+    /// CHECK: The signer set of the gateway, closed along with it if it exists
+    #[account(
+        mut,
+        seeds = [GATEWAY_SIGNERS_SEED, gateway.key().as_ref()],
+        bump,
+    )]
+    pub gateway_signers: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GATEWAY_BOND_SEED, gateway.key().as_ref()],
+        bump,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key(),
+        constraint = authority_token_account.mint == gateway.bond_mint,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_withdraw_gateway_bond(ctx: Context<WithdrawGatewayBond>) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= gateway.bond_unlocks_at,
+        RecurringPaymentsError::BondLocked
+    );
+
+    let seeds = &[GATEWAY_SEED, gateway.authority.as_ref(), &[gateway.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let amount = ctx.accounts.bond_vault.amount;
+
+    // Return whatever is left of the bond
+    if amount > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.bond_vault.to_account_info(),
+            to: ctx.accounts.authority_token_account.to_account_info(),
+            authority: gateway.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+    }
+
+    // Close the bond vault and return its rent
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.bond_vault.to_account_info(),
+        destination: ctx.accounts.authority.to_account_info(),
+        authority: gateway.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx)?;
+
+    close_gateway_signers(&ctx.accounts.gateway_signers, &ctx.accounts.authority)?;
+
+    let config = &mut ctx.accounts.config;
+    config.bonded_gateways = config.bonded_gateways.saturating_sub(1);
+
+    emit!(GatewayBondWithdrawn {
+        gateway: gateway.key(),
+        authority: gateway.authority,
+        amount,
+    });
+
+    msg!(
+        "Withdrew bond of {} and closed gateway {:?}",
+        amount,
+        gateway.key()
+    );
+
+    Ok(())
+}
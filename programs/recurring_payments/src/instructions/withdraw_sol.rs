@@ -0,0 +1,77 @@
+use crate::{constants::*, error::RecurringPaymentsError, state::*};
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+};
+
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [USER_PAYMENT_SEED, owner.key().as_ref(), user_payment.token_mint.as_ref()],
+        bump = user_payment.bump,
+        constraint = user_payment.owner == owner.key(),
+        constraint = user_payment.is_native_sol() @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub user_payment: Account<'info, UserPayment>,
+
+    #[account(
+        mut,
+        seeds = [SOL_VAULT_SEED, user_payment.key().as_ref()],
+        bump,
+        address = user_payment.vault @ RecurringPaymentsError::VaultNotFound,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_withdraw_sol(ctx: Context<WithdrawSol>, amount: u64) -> Result<()> {
+    require!(amount > 0, RecurringPaymentsError::InvalidAmount);
+
+    // The vault must either be emptied or stay rent exempt
+    let remaining = ctx
+        .accounts
+        .sol_vault
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(RecurringPaymentsError::InsufficientBalance)?;
+    require!(
+        remaining == 0 || remaining >= Rent::get()?.minimum_balance(0),
+        RecurringPaymentsError::InsufficientBalance
+    );
+
+    let user_payment_key = ctx.accounts.user_payment.key();
+    let seeds = &[
+        SOL_VAULT_SEED,
+        user_payment_key.as_ref(),
+        &[ctx.bumps.sol_vault],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.sol_vault.to_account_info(),
+        to: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.system_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    system_program::transfer(cpi_ctx, amount)?;
+
+    emit!(VaultWithdrawn {
+        user_payment: user_payment_key,
+        amount,
+        balance: remaining,
+    });
+
+    msg!(
+        "Withdrew {} lamports from SOL vault, balance: {}",
+        amount,
+        remaining
+    );
+
+    Ok(())
+}
@@ -8,6 +8,7 @@
 pub mod constants;
 pub mod error;
 pub mod instructions;
+pub mod oracle;
 pub mod state;
 pub mod utils;
 
@@ -51,12 +52,22 @@ pub mod recurring_payments {
         ctx: Context<CreatePaymentPolicy>,
         policy_type: PolicyType,
         memo: [u8; 64],
+        hold_seconds: u32,
     ) -> Result<()> {
-        instructions::create_payment_policy::handler_create_payment_policy(ctx, policy_type, memo)
+        instructions::create_payment_policy::handler_create_payment_policy(
+            ctx,
+            policy_type,
+            memo,
+            hold_seconds,
+        )
     }
 
-    pub fn execute_payment(ctx: Context<ExecutePayment>) -> Result<()> {
-        instructions::execute_payment::handler_execute_payment(ctx)
+    pub fn execute_payment(
+        ctx: Context<ExecutePayment>,
+        amount: Option<u64>,
+        invoice_reference: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::execute_payment::handler_execute_payment(ctx, amount, invoice_reference)
     }
 
     pub fn change_payment_policy_status(
@@ -84,6 +95,406 @@ pub mod recurring_payments {
     pub fn change_gateway_fee_recipient(ctx: Context<ChangeGatewayFeeRecipient>) -> Result<()> {
         instructions::change_gateway_fee_recipient::handler_change_gateway_fee_recipient(ctx)
     }
+
+    pub fn accept_policy_fees(ctx: Context<AcceptPolicyFees>, policy_id: u32) -> Result<()> {
+        instructions::accept_policy_fees::handler_accept_policy_fees(ctx, policy_id)
+    }
+
+    pub fn migrate_policy_gateway(
+        ctx: Context<MigratePolicyGateway>,
+        policy_id: u32,
+    ) -> Result<()> {
+        instructions::migrate_policy_gateway::handler_migrate_policy_gateway(ctx, policy_id)
+    }
+
+    pub fn migrate_gateway_policies(ctx: Context<MigrateGatewayPolicies>) -> Result<()> {
+        instructions::migrate_gateway_policies::handler_migrate_gateway_policies(ctx)
+    }
+
+    pub fn set_gateway_bond_config(
+        ctx: Context<SetGatewayBondConfig>,
+        bond_amount: u64,
+        cooldown_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_gateway_bond_config::handler_set_gateway_bond_config(
+            ctx,
+            bond_amount,
+            cooldown_seconds,
+        )
+    }
+
+    pub fn register_gateway(
+        ctx: Context<RegisterGateway>,
+        gateway_fee_bps: u16,
+        name: [u8; 32],
+        url: [u8; 64],
+    ) -> Result<()> {
+        instructions::register_gateway::handler_register_gateway(ctx, gateway_fee_bps, name, url)
+    }
+
+    pub fn approve_gateway(ctx: Context<ApproveGateway>) -> Result<()> {
+        instructions::approve_gateway::handler_approve_gateway(ctx)
+    }
+
+    pub fn slash_gateway_bond(ctx: Context<SlashGatewayBond>, amount: u64) -> Result<()> {
+        instructions::slash_gateway_bond::handler_slash_gateway_bond(ctx, amount)
+    }
+
+    pub fn deregister_gateway(ctx: Context<DeregisterGateway>) -> Result<()> {
+        instructions::deregister_gateway::handler_deregister_gateway(ctx)
+    }
+
+    pub fn withdraw_gateway_bond(ctx: Context<WithdrawGatewayBond>) -> Result<()> {
+        instructions::withdraw_gateway_bond::handler_withdraw_gateway_bond(ctx)
+    }
+
+    pub fn create_gateway_signers(ctx: Context<CreateGatewaySigners>) -> Result<()> {
+        instructions::create_gateway_signers::handler_create_gateway_signers(ctx)
+    }
+
+    pub fn add_gateway_signer(
+        ctx: Context<AddGatewaySigner>,
+        signer: Pubkey,
+        role: GatewaySignerRole,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::add_gateway_signer::handler_add_gateway_signer(ctx, signer, role, expires_at)
+    }
+
+    pub fn remove_gateway_signer(ctx: Context<RemoveGatewaySigner>, signer: Pubkey) -> Result<()> {
+        instructions::remove_gateway_signer::handler_remove_gateway_signer(ctx, signer)
+    }
+
+    pub fn report_payment_failure(
+        ctx: Context<ReportPaymentFailure>,
+        reason_code: u16,
+    ) -> Result<()> {
+        instructions::report_payment_failure::handler_report_payment_failure(ctx, reason_code)
+    }
+
+    pub fn set_gateway_keeper_settings(
+        ctx: Context<SetGatewayKeeperSettings>,
+        keeper_grace_seconds: i64,
+        keeper_bounty_bps: u16,
+    ) -> Result<()> {
+        instructions::set_gateway_keeper_settings::handler_set_gateway_keeper_settings(
+            ctx,
+            keeper_grace_seconds,
+            keeper_bounty_bps,
+        )
+    }
+
+    pub fn refund_payment(ctx: Context<RefundPayment>, record_id: u32, amount: u64) -> Result<()> {
+        instructions::refund_payment::handler_refund_payment(ctx, record_id, amount)
+    }
+
+    pub fn set_policy_hold_period(
+        ctx: Context<SetPolicyHoldPeriod>,
+        hold_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_policy_hold_period::handler_set_policy_hold_period(ctx, hold_seconds)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+        instructions::open_dispute::handler_open_dispute(ctx)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, release_to_recipient: bool) -> Result<()> {
+        instructions::resolve_dispute::handler_resolve_dispute(ctx, release_to_recipient)
+    }
+
+    pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
+        instructions::release_escrow::handler_release_escrow(ctx)
+    }
+
+    pub fn create_user_payment_vault(ctx: Context<CreateUserPaymentVault>) -> Result<()> {
+        instructions::create_user_payment_vault::handler_create_user_payment_vault(ctx)
+    }
+
+    pub fn deposit_to_vault(ctx: Context<DepositToVault>, amount: u64) -> Result<()> {
+        instructions::deposit_to_vault::handler_deposit_to_vault(ctx, amount)
+    }
+
+    pub fn withdraw_from_vault(ctx: Context<WithdrawFromVault>, amount: u64) -> Result<()> {
+        instructions::withdraw_from_vault::handler_withdraw_from_vault(ctx, amount)
+    }
+
+    pub fn create_sol_user_payment(ctx: Context<CreateSolUserPayment>) -> Result<()> {
+        instructions::create_sol_user_payment::handler_create_sol_user_payment(ctx)
+    }
+
+    pub fn deposit_sol(ctx: Context<DepositSol>, amount: u64) -> Result<()> {
+        instructions::deposit_sol::handler_deposit_sol(ctx, amount)
+    }
+
+    pub fn withdraw_sol(ctx: Context<WithdrawSol>, amount: u64) -> Result<()> {
+        instructions::withdraw_sol::handler_withdraw_sol(ctx, amount)
+    }
+
+    pub fn execute_sol_payment(
+        ctx: Context<ExecuteSolPayment>,
+        amount: Option<u64>,
+        invoice_reference: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::execute_sol_payment::handler_execute_sol_payment(
+            ctx,
+            amount,
+            invoice_reference,
+        )
+    }
+
+    pub fn stop_stream(ctx: Context<StopStream>) -> Result<()> {
+        instructions::stop_stream::handler_stop_stream(ctx)
+    }
+
+    pub fn submit_usage_report(ctx: Context<SubmitUsageReport>, units: u64) -> Result<()> {
+        instructions::submit_usage_report::handler_submit_usage_report(ctx, units)
+    }
+
+    pub fn set_spending_limit(
+        ctx: Context<SetSpendingLimit>,
+        limit: u64,
+        period_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_spending_limit::handler_set_spending_limit(ctx, limit, period_seconds)
+    }
+
+    pub fn create_recipient_spending_cap(
+        ctx: Context<CreateRecipientSpendingCap>,
+        recipient: Pubkey,
+        limit: u64,
+        period_seconds: i64,
+    ) -> Result<()> {
+        instructions::create_recipient_spending_cap::handler_create_recipient_spending_cap(
+            ctx,
+            recipient,
+            limit,
+            period_seconds,
+        )
+    }
+
+    pub fn close_recipient_spending_cap(ctx: Context<CloseRecipientSpendingCap>) -> Result<()> {
+        instructions::close_recipient_spending_cap::handler_close_recipient_spending_cap(ctx)
+    }
+
+    pub fn update_user_payment_token_account(
+        ctx: Context<UpdateUserPaymentTokenAccount>,
+    ) -> Result<()> {
+        instructions::update_user_payment_token_account::handler_update_user_payment_token_account(
+            ctx,
+        )
+    }
+
+    pub fn set_user_payment_active(
+        ctx: Context<SetUserPaymentActive>,
+        is_active: bool,
+    ) -> Result<()> {
+        instructions::set_user_payment_active::handler_set_user_payment_active(ctx, is_active)
+    }
+
+    pub fn close_user_payment(ctx: Context<CloseUserPayment>) -> Result<()> {
+        instructions::close_user_payment::handler_close_user_payment(ctx)
+    }
+
+    pub fn set_close_policy_bounty(
+        ctx: Context<SetClosePolicyBounty>,
+        bounty_lamports: u64,
+    ) -> Result<()> {
+        instructions::set_close_policy_bounty::handler_set_close_policy_bounty(ctx, bounty_lamports)
+    }
+
+    pub fn close_finished_policy(ctx: Context<CloseFinishedPolicy>) -> Result<()> {
+        instructions::close_finished_policy::handler_close_finished_policy(ctx)
+    }
+
+    pub fn extend_policy_term(
+        ctx: Context<ExtendPolicyTerm>,
+        ends_at: i64,
+        max_renewals: Option<u32>,
+    ) -> Result<()> {
+        instructions::extend_policy_term::handler_extend_policy_term(ctx, ends_at, max_renewals)
+    }
+
+    pub fn pause_payment_policy_until(
+        ctx: Context<PausePaymentPolicyUntil>,
+        until: i64,
+    ) -> Result<()> {
+        instructions::pause_payment_policy_until::handler_pause_payment_policy_until(ctx, until)
+    }
+
+    pub fn set_policy_max_pause(
+        ctx: Context<SetPolicyMaxPause>,
+        max_pause_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_policy_max_pause::handler_set_policy_max_pause(ctx, max_pause_seconds)
+    }
+
+    pub fn recipient_set_policy_status(
+        ctx: Context<RecipientSetPolicyStatus>,
+        new_status: PaymentStatus,
+        reason_code: u16,
+    ) -> Result<()> {
+        instructions::recipient_set_policy_status::handler_recipient_set_policy_status(
+            ctx,
+            new_status,
+            reason_code,
+        )
+    }
+
+    pub fn create_recipient_settings(
+        ctx: Context<CreateRecipientSettings>,
+        require_acceptance: bool,
+    ) -> Result<()> {
+        instructions::create_recipient_settings::handler_create_recipient_settings(
+            ctx,
+            require_acceptance,
+        )
+    }
+
+    pub fn set_recipient_require_acceptance(
+        ctx: Context<SetRecipientRequireAcceptance>,
+        require_acceptance: bool,
+    ) -> Result<()> {
+        instructions::set_recipient_require_acceptance::handler_set_recipient_require_acceptance(
+            ctx,
+            require_acceptance,
+        )
+    }
+
+    pub fn accept_policy(ctx: Context<AcceptPolicy>) -> Result<()> {
+        instructions::accept_policy::handler_accept_policy(ctx)
+    }
+
+    pub fn accept_policies(ctx: Context<AcceptPolicies>) -> Result<()> {
+        instructions::accept_policies::handler_accept_policies(ctx)
+    }
+
+    pub fn register_merchant(
+        ctx: Context<RegisterMerchant>,
+        name: [u8; 32],
+        url: [u8; 64],
+        logo_hash: [u8; 32],
+        support_contact: [u8; 64],
+    ) -> Result<()> {
+        instructions::register_merchant::handler_register_merchant(
+            ctx,
+            name,
+            url,
+            logo_hash,
+            support_contact,
+        )
+    }
+
+    pub fn update_merchant(
+        ctx: Context<UpdateMerchant>,
+        name: [u8; 32],
+        url: [u8; 64],
+        logo_hash: [u8; 32],
+        support_contact: [u8; 64],
+    ) -> Result<()> {
+        instructions::update_merchant::handler_update_merchant(
+            ctx,
+            name,
+            url,
+            logo_hash,
+            support_contact,
+        )
+    }
+
+    pub fn set_merchant_settlement_account(
+        ctx: Context<SetMerchantSettlementAccount>,
+    ) -> Result<()> {
+        instructions::set_merchant_settlement_account::handler_set_merchant_settlement_account(ctx)
+    }
+
+    pub fn remove_merchant_settlement_account(
+        ctx: Context<RemoveMerchantSettlementAccount>,
+        mint: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_merchant_settlement_account::handler_remove_merchant_settlement_account(
+            ctx, mint,
+        )
+    }
+
+    pub fn verify_merchant(ctx: Context<VerifyMerchant>) -> Result<()> {
+        instructions::verify_merchant::handler_verify_merchant(ctx)
+    }
+
+    pub fn revoke_merchant_verification(ctx: Context<RevokeMerchantVerification>) -> Result<()> {
+        instructions::revoke_merchant_verification::handler_revoke_merchant_verification(ctx)
+    }
+
+    pub fn add_to_denylist(
+        ctx: Context<AddToDenylist>,
+        address: Pubkey,
+        reason_code: u16,
+    ) -> Result<()> {
+        instructions::add_to_denylist::handler_add_to_denylist(ctx, address, reason_code)
+    }
+
+    pub fn remove_from_denylist(ctx: Context<RemoveFromDenylist>) -> Result<()> {
+        instructions::remove_from_denylist::handler_remove_from_denylist(ctx)
+    }
+
+    pub fn suspend_denylisted_policies(ctx: Context<SuspendDenylistedPolicies>) -> Result<()> {
+        instructions::suspend_denylisted_policies::handler_suspend_denylisted_policies(ctx)
+    }
+
+    pub fn set_config_role(
+        ctx: Context<SetConfigRole>,
+        role: ConfigRole,
+        holder: Pubkey,
+    ) -> Result<()> {
+        instructions::set_config_role::handler_set_config_role(ctx, role, holder)
+    }
+
+    pub fn set_emergency_pause(ctx: Context<SetEmergencyPause>, paused: bool) -> Result<()> {
+        instructions::set_emergency_pause::handler_set_emergency_pause(ctx, paused)
+    }
+
+    pub fn queue_config_change(
+        ctx: Context<QueueConfigChange>,
+        change: ConfigChange,
+    ) -> Result<()> {
+        instructions::queue_config_change::handler_queue_config_change(ctx, change)
+    }
+
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::execute_config_change::handler_execute_config_change(ctx)
+    }
+
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::cancel_config_change::handler_cancel_config_change(ctx)
+    }
+
+    pub fn backfill_gateway_policy_count(
+        ctx: Context<BackfillGatewayPolicyCount>,
+        complete: bool,
+    ) -> Result<()> {
+        instructions::backfill_gateway_policy_count::handler_backfill_gateway_policy_count(
+            ctx, complete,
+        )
+    }
+
+    pub fn top_up_gateway_bond(ctx: Context<TopUpGatewayBond>, amount: u64) -> Result<()> {
+        instructions::top_up_gateway_bond::handler_top_up_gateway_bond(ctx, amount)
+    }
+
+    pub fn refund_sol_payment(
+        ctx: Context<RefundSolPayment>,
+        record_id: u32,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::refund_sol_payment::handler_refund_sol_payment(ctx, record_id, amount)
+    }
+
+    pub fn close_user_payment_vault(ctx: Context<CloseUserPaymentVault>) -> Result<()> {
+        instructions::close_user_payment_vault::handler_close_user_payment_vault(ctx)
+    }
+
+    pub fn lift_compliance_suspension(ctx: Context<LiftComplianceSuspension>) -> Result<()> {
+        instructions::lift_compliance_suspension::handler_lift_compliance_suspension(ctx)
+    }
 }
 
 #[cfg(not(feature = "no-entrypoint"))]
@@ -0,0 +1,189 @@
+use crate::{constants::*, error::RecurringPaymentsError};
+use anchor_lang::prelude::*;
+
+/// The parts of a Pyth `PriceUpdateV2` account needed to price a payment.
+/// The account is parsed by hand to avoid pulling in the Pyth SDK.
+pub struct PriceUpdate {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub ema_price: i64,
+}
+
+impl PriceUpdate {
+    /// Anchor discriminator of `PriceUpdateV2`
+    const DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+    /// Offset of the verification level, after discriminator and write authority
+    const VERIFICATION_LEVEL_OFFSET: usize = 8 + 32;
+    /// Tag of `VerificationLevel::Full`, which has no payload
+    const VERIFICATION_LEVEL_FULL: u8 = 1;
+    /// Offset of the feed id of a fully verified update
+    const FEED_ID_OFFSET: usize = Self::VERIFICATION_LEVEL_OFFSET + 1;
+    /// Offset of the price message of a fully verified update, after its feed id
+    const PRICE_OFFSET: usize = Self::FEED_ID_OFFSET + 32;
+    /// price, conf, exponent, publish_time, prev_publish_time, ema_price
+    const PRICE_LEN: usize = 8 + 8 + 4 + 8 + 8 + 8;
+
+    /// Parse a fully verified price update of `feed_id` owned by the Pyth
+    /// receiver program
+    pub fn parse(price_feed: &AccountInfo, feed_id: &[u8; 32]) -> Result<Self> {
+        require_keys_eq!(
+            *price_feed.owner,
+            PYTH_RECEIVER_PROGRAM_ID,
+            RecurringPaymentsError::InvalidPriceFeed
+        );
+
+        let data = price_feed.try_borrow_data()?;
+        require!(
+            data.len() >= Self::PRICE_OFFSET + Self::PRICE_LEN
+                && data[..8] == Self::DISCRIMINATOR
+                && data[Self::VERIFICATION_LEVEL_OFFSET] == Self::VERIFICATION_LEVEL_FULL
+                && data[Self::FEED_ID_OFFSET..Self::PRICE_OFFSET] == feed_id[..],
+            RecurringPaymentsError::InvalidPriceFeed
+        );
+
+        let price = &data[Self::PRICE_OFFSET..];
+        Ok(Self {
+            price: i64::from_le_bytes(price[0..8].try_into().unwrap()),
+            conf: u64::from_le_bytes(price[8..16].try_into().unwrap()),
+            exponent: i32::from_le_bytes(price[16..20].try_into().unwrap()),
+            publish_time: i64::from_le_bytes(price[20..28].try_into().unwrap()),
+            ema_price: i64::from_le_bytes(price[36..44].try_into().unwrap()),
+        })
+    }
+
+    /// Check that the price is fresh, precise enough and within
+    /// `max_slippage_bps` of its moving average
+    pub fn validate(&self, max_slippage_bps: u16, now: i64) -> Result<()> {
+        require!(
+            self.price > 0 && self.ema_price > 0,
+            RecurringPaymentsError::InvalidPriceFeed
+        );
+        require!(
+            self.publish_time >= now.saturating_sub(MAX_PRICE_AGE_SECONDS),
+            RecurringPaymentsError::StalePrice
+        );
+
+        let price = self.price as u128;
+        let ema_price = self.ema_price as u128;
+        require!(
+            self.conf as u128 * 10000 <= price * MAX_PRICE_CONFIDENCE_BPS as u128,
+            RecurringPaymentsError::PriceConfidenceTooWide
+        );
+        require!(
+            price.abs_diff(ema_price) * 10000 <= ema_price * max_slippage_bps as u128,
+            RecurringPaymentsError::PriceSlippageExceeded
+        );
+
+        Ok(())
+    }
+
+    /// Convert a fiat amount with `FIAT_DECIMALS` into token base units
+    pub fn fiat_to_token_amount(&self, fiat_amount: u64, token_decimals: u8) -> Result<u64> {
+        let mut numerator = (fiat_amount as u128)
+            .checked_mul(10u128.pow(token_decimals as u32))
+            .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+        let mut denominator = (self.price as u128)
+            .checked_mul(10u128.pow(FIAT_DECIMALS))
+            .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+
+        // The price is `price * 10^exponent` fiat per whole token
+        let scale = 10u128
+            .checked_pow(self.exponent.unsigned_abs())
+            .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+        if self.exponent < 0 {
+            numerator = numerator
+                .checked_mul(scale)
+                .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+        } else {
+            denominator = denominator
+                .checked_mul(scale)
+                .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+        }
+
+        u64::try_from(numerator / denominator)
+            .map_err(|_| RecurringPaymentsError::ArithmeticOverflow.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price_update(price: i64, conf: u64, exponent: i32, ema_price: i64) -> PriceUpdate {
+        PriceUpdate {
+            price,
+            conf,
+            exponent,
+            publish_time: 1_000,
+            ema_price,
+        }
+    }
+
+    #[test]
+    fn parses_only_the_expected_feed() {
+        let key = Pubkey::new_unique();
+        let owner = PYTH_RECEIVER_PROGRAM_ID;
+        let mut lamports = 0;
+        let mut data = vec![0u8; PriceUpdate::PRICE_OFFSET + PriceUpdate::PRICE_LEN];
+        data[..8].copy_from_slice(&PriceUpdate::DISCRIMINATOR);
+        data[PriceUpdate::VERIFICATION_LEVEL_OFFSET] = PriceUpdate::VERIFICATION_LEVEL_FULL;
+        data[PriceUpdate::FEED_ID_OFFSET..PriceUpdate::PRICE_OFFSET].copy_from_slice(&[7; 32]);
+        data[PriceUpdate::PRICE_OFFSET..PriceUpdate::PRICE_OFFSET + 8]
+            .copy_from_slice(&100_000_000i64.to_le_bytes());
+        let price_feed = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        assert_eq!(
+            PriceUpdate::parse(&price_feed, &[7; 32]).unwrap().price,
+            100_000_000
+        );
+        let err = PriceUpdate::parse(&price_feed, &[8; 32]).err().unwrap();
+        assert_eq!(err, RecurringPaymentsError::InvalidPriceFeed.into());
+    }
+
+    #[test]
+    fn converts_fiat_at_the_oracle_price() {
+        // $150.00 per SOL with an exponent of -8, $10 is 1/15 SOL
+        let price = price_update(15_000_000_000, 0, -8, 15_000_000_000);
+        assert_eq!(
+            price.fiat_to_token_amount(10_000_000, 9).unwrap(),
+            66_666_666
+        );
+
+        // A stablecoin at exactly $1 converts one to one
+        let price = price_update(100_000_000, 0, -8, 100_000_000);
+        assert_eq!(
+            price.fiat_to_token_amount(10_000_000, 6).unwrap(),
+            10_000_000
+        );
+    }
+
+    #[test]
+    fn rejects_stale_imprecise_or_slipped_prices() {
+        let price = price_update(100_000_000, 0, -8, 100_000_000);
+        price.validate(100, 1_000).unwrap();
+
+        let err = price
+            .validate(100, 1_000 + MAX_PRICE_AGE_SECONDS + 1)
+            .unwrap_err();
+        assert_eq!(err, RecurringPaymentsError::StalePrice.into());
+
+        let wide = price_update(100_000_000, 3_000_000, -8, 100_000_000);
+        let err = wide.validate(100, 1_000).unwrap_err();
+        assert_eq!(err, RecurringPaymentsError::PriceConfidenceTooWide.into());
+
+        let slipped = price_update(102_000_000, 0, -8, 100_000_000);
+        let err = slipped.validate(100, 1_000).unwrap_err();
+        assert_eq!(err, RecurringPaymentsError::PriceSlippageExceeded.into());
+    }
+}
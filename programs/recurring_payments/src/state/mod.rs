@@ -14,7 +14,49 @@ pub enum PolicyType {
         max_renewals: Option<u32>,           // 5 bytes (1 + 4)
         payment_frequency: PaymentFrequency, // 9 bytes (1 + 8)
         next_payment_due: i64,               // 8 bytes
-        padding: [u8; 97],                   // 97 bytes padding
+        ends_at: i64, // 8 bytes - Last moment a payment may fall due, 0 if open ended
+        padding: [u8; 89], // 89 bytes padding
+    },
+    /// Pays continuously. The recipient can claim whatever has vested at any
+    /// time until `end`.
+    Stream {
+        rate_per_second: u64, // 8 bytes
+        start: i64,           // 8 bytes
+        end: i64,             // 8 bytes
+        withdrawn: u64,       // 8 bytes
+        padding: [u8; 96],    // 96 bytes padding
+    },
+    /// Bills the usage a gateway signer reports for each closed period, capped
+    /// by the payer at `max_units_per_period`.
+    Metered {
+        unit_price: u64,           // 8 bytes
+        max_units_per_period: u64, // 8 bytes
+        period: PaymentFrequency,  // 9 bytes (1 + 8)
+        next_payment_due: i64,     // 8 bytes - End of the current period
+        padding: [u8; 95],         // 95 bytes padding
+    },
+    /// Direct-debit mandate: the gateway or recipient pulls an amount of its
+    /// choosing each period, bounded by the payer's `max_amount` and an
+    /// optional cap over the last `rolling_periods` charged periods.
+    Variable {
+        max_amount: u64,          // 8 bytes
+        period: PaymentFrequency, // 9 bytes (1 + 8)
+        next_payment_due: i64,    // 8 bytes
+        rolling_periods: u8,      // 1 byte - 0 disables the rolling cap
+        rolling_cap: u64,         // 8 bytes
+        recent_amounts: [u64; 8], // 64 bytes - Latest charged amount first
+        padding: [u8; 30],        // 30 bytes padding
+    },
+    /// Subscription priced in fiat, converted to tokens at the oracle price
+    /// when each payment executes
+    FiatSubscription {
+        fiat_amount: u64,                    // 8 bytes - In FIAT_DECIMALS
+        price_feed: Pubkey,                  // 32 bytes - Pyth PriceUpdateV2 account
+        payment_frequency: PaymentFrequency, // 9 bytes (1 + 8)
+        next_payment_due: i64,               // 8 bytes
+        max_slippage_bps: u16,               // 2 bytes - Allowed deviation from the EMA price
+        feed_id: [u8; 32],                   // 32 bytes - Pyth feed the price update must carry
+        padding: [u8; 37],                   // 37 bytes padding
     },
     // Future variants can be added like this:
     // Installment {
@@ -41,6 +83,9 @@ pub enum PolicyType {
 }
 
 impl PolicyType {
+    /// Most periods a variable pull's rolling cap can span
+    pub const MAX_ROLLING_PERIODS: usize = 8;
+
     /// Each variant must be exactly this size (excluding enum discriminator)
     pub const VARIANT_SIZE: usize = 128;
 
@@ -73,9 +118,368 @@ impl PolicyType {
                     );
                 }
             }
+            PolicyType::Stream {
+                rate_per_second,
+                start,
+                end,
+                ..
+            } => {
+                require!(
+                    *rate_per_second > 0,
+                    crate::error::RecurringPaymentsError::InvalidAmount
+                );
+                require!(
+                    end > start,
+                    crate::error::RecurringPaymentsError::InvalidInterval
+                );
+
+                // The full stream has to be payable
+                Self::streamed(*rate_per_second, *start, *end, *end)?;
+            }
+            PolicyType::Metered {
+                unit_price,
+                max_units_per_period,
+                period,
+                ..
+            } => {
+                require!(
+                    *unit_price > 0,
+                    crate::error::RecurringPaymentsError::InvalidAmount
+                );
+                require!(
+                    *max_units_per_period > 0,
+                    crate::error::RecurringPaymentsError::InvalidAmount
+                );
+                period.validate()?;
+
+                // The payer's maximum exposure per period has to be payable
+                Self::metered_charge(*unit_price, *max_units_per_period, *max_units_per_period)?;
+            }
+            PolicyType::Variable {
+                max_amount,
+                period,
+                rolling_periods,
+                rolling_cap,
+                ..
+            } => {
+                require!(
+                    *max_amount > 0,
+                    crate::error::RecurringPaymentsError::InvalidAmount
+                );
+                period.validate()?;
+                require!(
+                    *rolling_periods as usize <= Self::MAX_ROLLING_PERIODS,
+                    crate::error::RecurringPaymentsError::InvalidInterval
+                );
+                require!(
+                    *rolling_periods == 0 || *rolling_cap > 0,
+                    crate::error::RecurringPaymentsError::InvalidAmount
+                );
+            }
+            PolicyType::FiatSubscription {
+                fiat_amount,
+                payment_frequency,
+                max_slippage_bps,
+                feed_id,
+                ..
+            } => {
+                require!(
+                    *fiat_amount > 0,
+                    crate::error::RecurringPaymentsError::InvalidAmount
+                );
+                payment_frequency.validate()?;
+                require!(
+                    *max_slippage_bps > 0 && *max_slippage_bps <= 10000,
+                    crate::error::RecurringPaymentsError::InvalidAmount
+                );
+                require!(
+                    *feed_id != [0; 32],
+                    crate::error::RecurringPaymentsError::InvalidPriceFeed
+                );
+            }
         }
         Ok(())
     }
+
+    /// The amount that can be charged at `now` and since when it is due
+    pub fn due_payment(&self, now: i64) -> Result<(u64, i64)> {
+        match self {
+            PolicyType::Subscription {
+                amount,
+                next_payment_due,
+                ends_at,
+                ..
+            } => {
+                require!(
+                    Self::within_term(*next_payment_due, *ends_at),
+                    crate::error::RecurringPaymentsError::PolicyExpired
+                );
+                Ok((*amount, *next_payment_due))
+            }
+            PolicyType::Stream {
+                rate_per_second,
+                start,
+                end,
+                withdrawn,
+                ..
+            } => {
+                let vested = Self::streamed(*rate_per_second, *start, *end, now)?;
+                Ok((vested.saturating_sub(*withdrawn), *start))
+            }
+            // The amount depends on the usage report of the closed period
+            PolicyType::Metered {
+                next_payment_due, ..
+            } => Ok((0, *next_payment_due)),
+            // The amount is chosen by whoever pulls the payment
+            PolicyType::Variable {
+                next_payment_due, ..
+            } => Ok((0, *next_payment_due)),
+            // The amount depends on the oracle price at execution
+            PolicyType::FiatSubscription {
+                next_payment_due, ..
+            } => Ok((0, *next_payment_due)),
+        }
+    }
+
+    /// Check an amount requested for a variable pull against the payer's
+    /// per-period maximum and rolling cap
+    pub fn check_requested_amount(&self, amount: u64) -> Result<()> {
+        match self {
+            PolicyType::Variable {
+                max_amount,
+                rolling_periods,
+                rolling_cap,
+                recent_amounts,
+                ..
+            } => {
+                require!(
+                    amount <= *max_amount,
+                    crate::error::RecurringPaymentsError::AmountExceedsMax
+                );
+                if *rolling_periods > 0 {
+                    let mut rolling_total = amount;
+                    for recent in recent_amounts.iter().take(*rolling_periods as usize - 1) {
+                        rolling_total = rolling_total
+                            .checked_add(*recent)
+                            .ok_or(crate::error::RecurringPaymentsError::ArithmeticOverflow)?;
+                    }
+                    require!(
+                        rolling_total <= *rolling_cap,
+                        crate::error::RecurringPaymentsError::RollingCapExceeded
+                    );
+                }
+                Ok(())
+            }
+            _ => err!(crate::error::RecurringPaymentsError::InvalidAmount),
+        }
+    }
+
+    /// Amount charged for the units reported in a metered period, capped by
+    /// the payer's maximum. Zero for other policy types.
+    pub fn usage_charge(&self, units: u64) -> Result<u64> {
+        match self {
+            PolicyType::Metered {
+                unit_price,
+                max_units_per_period,
+                ..
+            } => Self::metered_charge(*unit_price, *max_units_per_period, units),
+            _ => Ok(0),
+        }
+    }
+
+    /// The next payment and when it is due, if the policy still has one. Usage
+    /// based and variable policies may charge up to their cap, and a fiat priced
+    /// policy is estimated with the `last_amount` it was converted to.
+    pub fn upcoming_payment(&self, last_amount: u64) -> Result<Option<(u64, i64)>> {
+        let upcoming = match self {
+            PolicyType::Subscription {
+                amount,
+                next_payment_due,
+                ..
+            } => (*amount, *next_payment_due),
+            PolicyType::Stream {
+                rate_per_second,
+                start,
+                end,
+                withdrawn,
+                ..
+            } => (
+                Self::streamed(*rate_per_second, *start, *end, *end)?.saturating_sub(*withdrawn),
+                *end,
+            ),
+            PolicyType::Metered {
+                unit_price,
+                max_units_per_period,
+                next_payment_due,
+                ..
+            } => (
+                Self::metered_charge(*unit_price, *max_units_per_period, *max_units_per_period)?,
+                *next_payment_due,
+            ),
+            PolicyType::Variable {
+                max_amount,
+                next_payment_due,
+                ..
+            } => (*max_amount, *next_payment_due),
+            PolicyType::FiatSubscription {
+                next_payment_due, ..
+            } => (last_amount, *next_payment_due),
+        };
+        Ok(Some(upcoming).filter(|(amount, _)| *amount > 0))
+    }
+
+    /// Advance the policy after `amount` was charged at `now`. Returns whether
+    /// the policy has run its course.
+    pub fn advance(&mut self, amount: u64, payment_count: u32, now: i64) -> Result<bool> {
+        match self {
+            PolicyType::Subscription {
+                payment_frequency: period,
+                next_payment_due,
+                ..
+            }
+            | PolicyType::Metered {
+                period,
+                next_payment_due,
+                ..
+            }
+            | PolicyType::FiatSubscription {
+                payment_frequency: period,
+                next_payment_due,
+                ..
+            } => {
+                *next_payment_due =
+                    crate::utils::calculate_next_payment_due(*next_payment_due, period, now)?;
+            }
+            PolicyType::Stream { withdrawn, .. } => {
+                *withdrawn = withdrawn
+                    .checked_add(amount)
+                    .ok_or(crate::error::RecurringPaymentsError::ArithmeticOverflow)?;
+            }
+            PolicyType::Variable {
+                period,
+                next_payment_due,
+                recent_amounts,
+                ..
+            } => {
+                recent_amounts.rotate_right(1);
+                recent_amounts[0] = amount;
+                *next_payment_due =
+                    crate::utils::calculate_next_payment_due(*next_payment_due, period, now)?;
+            }
+        }
+        self.is_finished(payment_count, now)
+    }
+
+    /// Move the next due date to the first one at or after `until`, skipping
+    /// the periods in between without charging them
+    pub fn skip_periods_until(&mut self, until: i64) -> Result<()> {
+        match self {
+            PolicyType::Subscription {
+                payment_frequency: period,
+                next_payment_due,
+                ..
+            }
+            | PolicyType::Metered {
+                period,
+                next_payment_due,
+                ..
+            }
+            | PolicyType::Variable {
+                period,
+                next_payment_due,
+                ..
+            }
+            | PolicyType::FiatSubscription {
+                payment_frequency: period,
+                next_payment_due,
+                ..
+            } => {
+                *next_payment_due =
+                    crate::utils::calculate_next_payment_due(*next_payment_due, period, until - 1)?;
+                Ok(())
+            }
+            // A stream accrues continuously and has no periods to skip
+            PolicyType::Stream { .. } => {
+                err!(crate::error::RecurringPaymentsError::InvalidInterval)
+            }
+        }
+    }
+
+    /// Whether the policy has run its course and will never charge again
+    pub fn is_finished(&self, payment_count: u32, now: i64) -> Result<bool> {
+        match self {
+            // Without auto renewal and without a bounded term the first
+            // payment completes the policy
+            PolicyType::Subscription {
+                auto_renew,
+                max_renewals,
+                next_payment_due,
+                ends_at,
+                ..
+            } => Ok(
+                max_renewals.is_some_and(|max_renewal| payment_count >= max_renewal)
+                    || !Self::within_term(*next_payment_due, *ends_at)
+                    || (!auto_renew
+                        && max_renewals.is_none()
+                        && *ends_at == 0
+                        && payment_count >= 1),
+            ),
+            PolicyType::Stream {
+                rate_per_second,
+                start,
+                end,
+                withdrawn,
+                ..
+            } => {
+                Ok(now >= *end
+                    && *withdrawn >= Self::streamed(*rate_per_second, *start, *end, *end)?)
+            }
+            PolicyType::Metered { .. }
+            | PolicyType::Variable { .. }
+            | PolicyType::FiatSubscription { .. } => Ok(false),
+        }
+    }
+
+    /// End of the period the payer has paid for
+    pub fn period_end(&self) -> i64 {
+        match self {
+            PolicyType::Subscription {
+                next_payment_due, ..
+            }
+            | PolicyType::Metered {
+                next_payment_due, ..
+            }
+            | PolicyType::Variable {
+                next_payment_due, ..
+            }
+            | PolicyType::FiatSubscription {
+                next_payment_due, ..
+            } => *next_payment_due,
+            PolicyType::Stream { end, .. } => *end,
+        }
+    }
+
+    /// Whether a payment due at `next_payment_due` falls within a term ending
+    /// at `ends_at`, where 0 leaves the term open ended
+    pub fn within_term(next_payment_due: i64, ends_at: i64) -> bool {
+        ends_at == 0 || next_payment_due <= ends_at
+    }
+
+    /// Amount charged for `units` of a metered period
+    fn metered_charge(unit_price: u64, max_units_per_period: u64, units: u64) -> Result<u64> {
+        Ok(units
+            .min(max_units_per_period)
+            .checked_mul(unit_price)
+            .ok_or(crate::error::RecurringPaymentsError::ArithmeticOverflow)?)
+    }
+
+    /// Amount a stream has vested at `now`
+    fn streamed(rate_per_second: u64, start: i64, end: i64, now: i64) -> Result<u64> {
+        let elapsed = now.min(end).saturating_sub(start).max(0) as u64;
+        Ok(rate_per_second
+            .checked_mul(elapsed)
+            .ok_or(crate::error::RecurringPaymentsError::ArithmeticOverflow)?)
+    }
 }
 
 /// A status enum for installed payment policies indicating if payment can be made
@@ -83,6 +487,14 @@ impl PolicyType {
 pub enum PaymentStatus {
     Active,
     Paused,
+    /// Cancelled for good, can be closed by anyone once its period has ended
+    Cancelled,
+    /// Stopped by the recipient, only the recipient can lift it
+    Suspended,
+    /// Waiting for a recipient that requires acceptance to accept it
+    PendingAcceptance,
+    /// Stopped because a party is denylisted, only the Compliance role can lift it
+    ComplianceSuspended,
 }
 
 /// Simplify the payment frequency while also allowing a custom period as well,
@@ -101,14 +513,11 @@ pub enum PaymentFrequency {
 impl PaymentFrequency {
     /// Validates the payment frequency
     pub fn validate(&self) -> Result<()> {
-        match self {
-            PaymentFrequency::Custom(interval) => {
-                require!(
-                    *interval > 0,
-                    crate::error::RecurringPaymentsError::InvalidFrequency
-                );
-            }
-            _ => {}
+        if let PaymentFrequency::Custom(interval) = self {
+            require!(
+                *interval > 0,
+                crate::error::RecurringPaymentsError::InvalidFrequency
+            );
         }
         Ok(())
     }
@@ -131,7 +540,16 @@ pub struct UserPayment {
     pub updated_at: i64,
     pub is_active: bool,
     pub bump: u8,
-    pub padding: [u8; 256],
+    /// Prepaid vault that policies draw from instead of the delegated token account,
+    /// default while the user funds payments through token delegation
+    pub vault: Pubkey,
+    /// Cap on what all policies together may charge per period
+    pub spending_limit: SpendingLimit,
+    /// Where payments are drawn from
+    pub funding_mode: FundingMode,
+    /// Highest policy id handed out, never decreases so closed policies keep their PDA
+    pub last_policy_id: u32,
+    pub padding: [u8; 131],
 }
 
 impl UserPayment {
@@ -144,7 +562,50 @@ impl UserPayment {
         8 + // updated_at: i64
         1 + // is_active: bool
         1 + // bump: u8
-        256; // padding: [u8; 256]
+        32 + // vault: Pubkey
+        SpendingLimit::SIZE + // spending_limit: SpendingLimit
+        1 + // funding_mode: FundingMode
+        4 + // last_policy_id: u32
+        131; // padding: [u8; 131]
+
+    /// Whether payments are drawn from a prepaid vault
+    pub fn has_vault(&self) -> bool {
+        self.funding_mode != FundingMode::Delegate
+    }
+
+    /// Whether payments are made in native SOL from a lamport vault
+    pub fn is_native_sol(&self) -> bool {
+        self.funding_mode == FundingMode::NativeSol
+    }
+
+    /// The id the next policy is created with. Accounts created before ids were
+    /// tracked continue from their policy count.
+    pub fn next_policy_id(&self) -> u32 {
+        self.last_policy_id
+            .max(self.active_policies_count)
+            .saturating_add(1)
+    }
+
+    /// The token account payments are drawn from
+    pub fn funding_account(&self) -> Pubkey {
+        if self.has_vault() {
+            self.vault
+        } else {
+            self.token_account
+        }
+    }
+}
+
+/// How a user payment account funds its policies
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum FundingMode {
+    /// Tokens are pulled from the owner's token account through the payments delegate
+    #[default]
+    Delegate,
+    /// Tokens are drawn from a prepaid token vault owned by the user payment account
+    TokenVault,
+    /// Lamports are drawn from a prepaid SOL vault
+    NativeSol,
 }
 
 /// A gateway operator runs the service that triggers payment.
@@ -164,7 +625,22 @@ pub struct PaymentGateway {
     pub url: [u8; 64],
     /// This signer key is to execute payments
     pub signer: Pubkey,
-    pub padding: [u8; 128],
+    /// Number of payment policies that currently point at this gateway
+    pub active_policies_count: u32,
+    /// Mint of the bond posted on self-registration, default if no bond was posted
+    pub bond_mint: Pubkey,
+    /// Amount currently held in the bond vault
+    pub bond_amount: u64,
+    /// When the bond can be withdrawn after deregistering, zero while registered
+    pub bond_unlocks_at: i64,
+    /// How long a payment must be overdue before anyone may execute it, zero disables keepers
+    pub keeper_grace_seconds: i64,
+    /// Share of the gateway fee paid to a keeper that executes an overdue payment
+    pub keeper_bounty_bps: u16,
+    /// Whether `active_policies_count` covers every policy of the gateway.
+    /// Gateways created before policies were counted need a backfill first.
+    pub policy_count_complete: bool,
+    pub padding: [u8; 65],
 }
 
 impl PaymentGateway {
@@ -179,7 +655,90 @@ impl PaymentGateway {
         32 + // name: [u8; 32]
         64 + // url: [u8; 64]
         32 + // signer: Pubkey
-        128; // padding: [u8; 160]
+        4 + // active_policies_count: u32
+        32 + // bond_mint: Pubkey
+        8 + // bond_amount: u64
+        8 + // bond_unlocks_at: i64
+        8 + // keeper_grace_seconds: i64
+        2 + // keeper_bounty_bps: u16
+        1 + // policy_count_complete: bool
+        65; // padding: [u8; 65]
+}
+
+/// The role of an additional gateway signer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum GatewaySignerRole {
+    /// May only execute payments
+    Executor,
+    /// May execute payments and also act for the gateway, e.g. report failures
+    /// or issue refunds
+    Operator,
+}
+
+impl GatewaySignerRole {
+    /// Whether this role includes the permissions of the required role
+    pub fn allows(&self, required: GatewaySignerRole) -> bool {
+        *self == GatewaySignerRole::Operator || *self == required
+    }
+}
+
+/// A payment of a policy and how much of it was refunded
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PaymentReceipt {
+    pub record_id: u32,
+    pub amount: u64,
+    pub refunded: u64,
+}
+
+impl PaymentReceipt {
+    pub const SIZE: usize = 4 + // record_id: u32
+        8 + // amount: u64
+        8; // refunded: u64
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct GatewaySignerEntry {
+    pub signer: Pubkey,
+    pub role: GatewaySignerRole,
+    /// Unix timestamp after which the key is no longer valid, zero for no expiry
+    pub expires_at: i64,
+}
+
+impl GatewaySignerEntry {
+    pub const SIZE: usize = 32 + // signer: Pubkey
+        1 + // role: GatewaySignerRole
+        8; // expires_at: i64
+
+    pub fn is_valid(&self, now: i64) -> bool {
+        self.expires_at == 0 || now < self.expires_at
+    }
+}
+
+/// The set of additional keys that may sign for a gateway. Allows several
+/// concurrent keepers and overlapping key rotations.
+#[account]
+pub struct GatewaySigners {
+    pub gateway: Pubkey,
+    pub signers: Vec<GatewaySignerEntry>,
+    pub bump: u8,
+    pub padding: [u8; 64],
+}
+
+impl GatewaySigners {
+    pub const MAX_SIGNERS: usize = 10;
+
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // gateway: Pubkey
+        4 + GatewaySignerEntry::SIZE * Self::MAX_SIGNERS + // signers: Vec<GatewaySignerEntry>
+        1 + // bump: u8
+        64; // padding: [u8; 64]
+
+    /// Whether `key` is a valid signer with at least the required role
+    pub fn authorizes(&self, key: &Pubkey, required: GatewaySignerRole, now: i64) -> bool {
+        self.signers
+            .iter()
+            .any(|entry| entry.signer == *key && entry.is_valid(now) && entry.role.allows(required))
+    }
 }
 
 /// This structure connects a UserPayment (user/mint) with a Policy, a Gateway.
@@ -199,7 +758,36 @@ pub struct PaymentPolicy {
     pub updated_at: i64,
     pub policy_id: u32,
     pub bump: u8,
-    pub padding: [u8; 256],
+    /// Gateway fee rate agreed by the payer. Execution charges the lower of
+    /// this and the gateway's current rate.
+    pub gateway_fee_bps: u16,
+    /// Protocol fee rate agreed by the payer. Execution charges the lower of
+    /// this and the program's current rate.
+    pub protocol_fee_bps: u16,
+    /// Amount returned to the payer through refunds
+    pub total_refunded: u64,
+    /// How long the recipient's share of each payment is held in escrow, zero pays out immediately
+    pub hold_seconds: u32,
+    /// While paused, when the policy resumes on its own, zero if it is paused indefinitely
+    pub paused_until: i64,
+    /// Longest pause the recipient allows, zero for no limit
+    pub max_pause_seconds: u32,
+    /// Gateway that had verified the recipient's merchant profile when the policy was created
+    pub merchant_verified_by: Option<Pubkey>,
+    /// Whether the payer agreed to the fee rates above. Policies created before
+    /// fee rates were snapshotted have none and pay the current rates.
+    pub fees_snapshotted: bool,
+    /// Whether the policy is included in its gateway's `active_policies_count`
+    pub counted_by_gateway: bool,
+    /// The latest payments, newest first. Only these can be refunded.
+    pub recent_payments: [PaymentReceipt; PaymentPolicy::REFUNDABLE_PAYMENTS],
+    /// Escrows of this policy that are held or disputed, the policy can not be
+    /// closed until they are settled
+    pub open_escrows: u32,
+    /// Whether the recipient had suspended the policy before compliance did,
+    /// lifting the compliance suspension restores it
+    pub suspended_by_recipient: bool,
+    pub padding: [u8; 108],
 }
 
 impl PaymentPolicy {
@@ -216,31 +804,581 @@ impl PaymentPolicy {
         8 + // updated_at: i64
         4 + // policy_id: u32
         1 + // bump: u8
-        256; // padding: [u8; 256]
+        2 + // gateway_fee_bps: u16
+        2 + // protocol_fee_bps: u16
+        8 + // total_refunded: u64
+        4 + // hold_seconds: u32
+        8 + // paused_until: i64
+        4 + // max_pause_seconds: u32
+        33 + // merchant_verified_by: Option<Pubkey>
+        1 + // fees_snapshotted: bool
+        1 + // counted_by_gateway: bool
+        PaymentReceipt::SIZE * Self::REFUNDABLE_PAYMENTS + // recent_payments: [PaymentReceipt; 4]
+        4 + // open_escrows: u32
+        1 + // suspended_by_recipient: bool
+        108; // padding: [u8; 108]
+
+    /// How many of the latest payments can be refunded
+    pub const REFUNDABLE_PAYMENTS: usize = 4;
+
+    /// Remember a payment so it can be refunded later
+    pub fn push_receipt(&mut self, record_id: u32, amount: u64) {
+        self.recent_payments.rotate_right(1);
+        self.recent_payments[0] = PaymentReceipt {
+            record_id,
+            amount,
+            refunded: 0,
+        };
+    }
+
+    /// The receipt of payment `record_id`, if it is recent enough to be refunded
+    pub fn receipt_mut(&mut self, record_id: u32) -> Option<&mut PaymentReceipt> {
+        self.recent_payments
+            .iter_mut()
+            .find(|receipt| receipt.record_id != 0 && receipt.record_id == record_id)
+    }
+
+    /// Book a refund of `amount` against payment `record_id`. A payment can
+    /// be refunded at most up to the amount it charged.
+    pub fn book_refund(&mut self, record_id: u32, amount: u64) -> Result<()> {
+        let total_refunded = self
+            .total_refunded
+            .checked_add(amount)
+            .ok_or(crate::error::RecurringPaymentsError::ArithmeticOverflow)?;
+        let total_paid = self.total_paid;
+
+        let receipt = self
+            .receipt_mut(record_id)
+            .ok_or(crate::error::RecurringPaymentsError::RefundWindowClosed)?;
+        let refunded = receipt
+            .refunded
+            .checked_add(amount)
+            .ok_or(crate::error::RecurringPaymentsError::ArithmeticOverflow)?;
+        require!(
+            refunded <= receipt.amount && total_refunded <= total_paid,
+            crate::error::RecurringPaymentsError::RefundExceedsPaid
+        );
+
+        receipt.refunded = refunded;
+        self.total_refunded = total_refunded;
+        Ok(())
+    }
+
+    /// Book the escrowed share of payment `record_id` returned to the payer by
+    /// a dispute. It is capped like any refund, so what was already refunded
+    /// can not be returned twice. A payment that has left the refund window
+    /// only counts against the total paid.
+    pub fn book_dispute_refund(&mut self, record_id: u32, amount: u64) -> Result<()> {
+        if self.receipt_mut(record_id).is_some() {
+            return self.book_refund(record_id, amount);
+        }
+
+        let total_refunded = self
+            .total_refunded
+            .checked_add(amount)
+            .ok_or(crate::error::RecurringPaymentsError::ArithmeticOverflow)?;
+        require!(
+            total_refunded <= self.total_paid,
+            crate::error::RecurringPaymentsError::RefundExceedsPaid
+        );
+        self.total_refunded = total_refunded;
+        Ok(())
+    }
+
+    /// The gateway and protocol fee rates a payment is charged, given the
+    /// current rates. Never more than the rates the payer agreed to.
+    pub fn fee_rates(&self, gateway_fee_bps: u16, protocol_fee_bps: u16) -> (u16, u16) {
+        if !self.fees_snapshotted {
+            return (gateway_fee_bps, protocol_fee_bps);
+        }
+        (
+            self.gateway_fee_bps.min(gateway_fee_bps),
+            self.protocol_fee_bps.min(protocol_fee_bps),
+        )
+    }
+
+    /// Record the fee rates the payer agrees to
+    pub fn snapshot_fees(&mut self, gateway_fee_bps: u16, protocol_fee_bps: u16) {
+        self.gateway_fee_bps = gateway_fee_bps;
+        self.protocol_fee_bps = protocol_fee_bps;
+        self.fees_snapshotted = true;
+    }
 }
 
-/// This is a unique global program configuration managed by an admin that
-/// defines the protocol fees and potentially more.
+/// Caps the amount charged within any window of `period_seconds`. Charges are
+/// booked in `BUCKETS` time buckets, where the current bucket and the ones
+/// before it always span at least a whole period. A charge therefore counts
+/// until it is at least a period old, and at most one bucket longer.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct SpendingLimit {
+    /// Most that may be charged per period, zero for no limit
+    pub limit: u64,
+    pub period_seconds: i64,
+    /// Number of the bucket of the latest charge, counted from the unix epoch
+    pub latest_bucket: i64,
+    /// Amount charged per bucket, indexed by bucket number modulo `BUCKETS`
+    pub buckets: [u64; SpendingLimit::BUCKETS],
+}
+
+impl SpendingLimit {
+    pub const BUCKETS: usize = 8;
+
+    pub const SIZE: usize = 8 + // limit: u64
+        8 + // period_seconds: i64
+        8 + // latest_bucket: i64
+        8 * Self::BUCKETS; // buckets: [u64; BUCKETS]
+
+    pub fn new(limit: u64, period_seconds: i64) -> Result<Self> {
+        require!(
+            limit == 0 || period_seconds > 0,
+            crate::error::RecurringPaymentsError::InvalidInterval
+        );
+        Ok(Self {
+            limit,
+            period_seconds,
+            ..Default::default()
+        })
+    }
+
+    /// Width of a bucket, so that all buckets but the current one cover a period
+    fn bucket_seconds(&self) -> i64 {
+        let spans = Self::BUCKETS as i64 - 1;
+        (self.period_seconds + spans - 1) / spans
+    }
+
+    /// Book `amount` in the window ending at `now`. Returns false, leaving the
+    /// limit untouched, if the amount does not fit.
+    pub fn try_charge(&mut self, amount: u64, now: i64) -> Result<bool> {
+        if self.limit == 0 {
+            return Ok(true);
+        }
+
+        // Drop the buckets that fell out of the window since the latest charge
+        let bucket = now.div_euclid(self.bucket_seconds());
+        let mut buckets = self.buckets;
+        let expired = bucket.saturating_sub(self.latest_bucket);
+        if expired >= Self::BUCKETS as i64 {
+            buckets = [0; Self::BUCKETS];
+        } else {
+            for number in self.latest_bucket + 1..=bucket {
+                buckets[number.rem_euclid(Self::BUCKETS as i64) as usize] = 0;
+            }
+        }
+
+        let mut spent = amount;
+        for charged in buckets {
+            spent = spent
+                .checked_add(charged)
+                .ok_or(crate::error::RecurringPaymentsError::ArithmeticOverflow)?;
+        }
+        if spent > self.limit {
+            return Ok(false);
+        }
+
+        let slot = &mut buckets[bucket.rem_euclid(Self::BUCKETS as i64) as usize];
+        *slot = slot
+            .checked_add(amount)
+            .ok_or(crate::error::RecurringPaymentsError::ArithmeticOverflow)?;
+        self.buckets = buckets;
+        self.latest_bucket = self.latest_bucket.max(bucket);
+        Ok(true)
+    }
+}
+
+/// A payer's cap on what all of their policies may pay a single recipient
 #[account]
-pub struct ProgramConfig {
-    pub admin: Pubkey,
-    pub fee_recipient: Pubkey,
-    pub protocol_fee_bps: u16,
-    pub max_policies_per_user: u32,
-    pub emergency_pause: bool,
+pub struct RecipientSpendingCap {
+    pub user_payment: Pubkey,
+    pub recipient: Pubkey,
+    pub spending_limit: SpendingLimit,
+    pub created_at: i64,
     pub bump: u8,
-    pub padding: [u8; 256],
+    pub padding: [u8; 32],
 }
 
-impl ProgramConfig {
+impl RecipientSpendingCap {
     pub const SIZE: usize = 8 + // discriminator
-        32 + // admin: Pubkey
-        32 + // fee_recipient: Pubkey
-        2 + // protocol_fee_bps: u16
-        4 + // max_policies_per_user: u32
-        1 + // emergency_pause: bool
+        32 + // user_payment: Pubkey
+        32 + // recipient: Pubkey
+        SpendingLimit::SIZE + // spending_limit: SpendingLimit
+        8 + // created_at: i64
         1 + // bump: u8
-        256; // padding: [u8; 256]
+        32; // padding: [u8; 32]
+}
+
+/// Marks an address that may neither pay nor be paid, e.g. a sanctioned
+/// entity or a compromised key. Blocked for as long as the account exists.
+#[account]
+pub struct DenylistEntry {
+    pub address: Pubkey,
+    /// Why the address is blocked, the codes are defined off-chain
+    pub reason_code: u16,
+    pub added_by: Pubkey,
+    pub created_at: i64,
+    pub bump: u8,
+    pub padding: [u8; 32],
+}
+
+impl DenylistEntry {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // address: Pubkey
+        2 + // reason_code: u16
+        32 + // added_by: Pubkey
+        8 + // created_at: i64
+        1 + // bump: u8
+        32; // padding: [u8; 32]
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SettlementAccount {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+}
+
+impl SettlementAccount {
+    pub const SIZE: usize = 32 + // mint: Pubkey
+        32; // token_account: Pubkey
+}
+
+/// Public profile of a recipient, so wallets can show who is charging the user
+#[account]
+pub struct Merchant {
+    pub authority: Pubkey,
+    pub name: [u8; 32],
+    pub url: [u8; 64],
+    /// Hash of the logo image, lets clients check the logo served from `url`
+    pub logo_hash: [u8; 32],
+    pub support_contact: [u8; 64],
+    /// Token account payments should settle to, at most one per mint
+    pub settlement_accounts: Vec<SettlementAccount>,
+    /// Gateway that attested to this profile, cleared when the profile changes
+    pub verified_by: Option<Pubkey>,
+    pub verified_at: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+    pub padding: [u8; 64],
+}
+
+impl Merchant {
+    pub const MAX_SETTLEMENT_ACCOUNTS: usize = 8;
+
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // authority: Pubkey
+        32 + // name: [u8; 32]
+        64 + // url: [u8; 64]
+        32 + // logo_hash: [u8; 32]
+        64 + // support_contact: [u8; 64]
+        4 + SettlementAccount::SIZE * Self::MAX_SETTLEMENT_ACCOUNTS + // settlement_accounts: Vec<SettlementAccount>
+        33 + // verified_by: Option<Pubkey>
+        8 + // verified_at: i64
+        8 + // created_at: i64
+        8 + // updated_at: i64
+        1 + // bump: u8
+        64; // padding: [u8; 64]
+}
+
+/// Settings a recipient applies to every policy that pays them
+#[account]
+pub struct RecipientSettings {
+    pub recipient: Pubkey,
+    /// New policies wait in `PendingAcceptance` until the recipient accepts them
+    pub require_acceptance: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+    pub padding: [u8; 64],
+}
+
+impl RecipientSettings {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // recipient: Pubkey
+        1 + // require_acceptance: bool
+        8 + // created_at: i64
+        8 + // updated_at: i64
+        1 + // bump: u8
+        64; // padding: [u8; 64]
+}
+
+/// A status enum for payments held in escrow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum EscrowStatus {
+    /// Waiting for the hold period to end
+    Held,
+    /// Frozen by the payer until the gateway resolves the dispute
+    Disputed,
+}
+
+/// Holds the recipient's share of a single payment while the payer may still
+/// dispute it. The funds sit in a token vault owned by this account.
+#[account]
+pub struct PaymentEscrow {
+    pub payment_policy: Pubkey,
+    pub record_id: u32,
+    pub recipient: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub release_at: i64,
+    pub status: EscrowStatus,
+    /// Who paid the rent for the escrow, receives it back on settlement
+    pub rent_payer: Pubkey,
+    pub created_at: i64,
+    pub bump: u8,
+    pub padding: [u8; 64],
+}
+
+impl PaymentEscrow {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // payment_policy: Pubkey
+        4 + // record_id: u32
+        32 + // recipient: Pubkey
+        32 + // token_mint: Pubkey
+        8 + // amount: u64
+        8 + // release_at: i64
+        1 + // status: EscrowStatus
+        32 + // rent_payer: Pubkey
+        8 + // created_at: i64
+        1 + // bump: u8
+        64; // padding: [u8; 64]
+}
+
+/// Usage a gateway signer reported for one closed period of a metered policy.
+/// It is charged by the payment with the same record id and kept as a receipt.
+#[account]
+pub struct UsageReport {
+    pub payment_policy: Pubkey,
+    pub record_id: u32,
+    pub period_end: i64,
+    pub units: u64,
+    pub reporter: Pubkey,
+    pub reported_at: i64,
+    /// Amount charged for the report, zero until the payment is executed
+    pub amount_charged: u64,
+    pub charged_at: i64,
+    pub bump: u8,
+    pub padding: [u8; 64],
+}
+
+impl UsageReport {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // payment_policy: Pubkey
+        4 + // record_id: u32
+        8 + // period_end: i64
+        8 + // units: u64
+        32 + // reporter: Pubkey
+        8 + // reported_at: i64
+        8 + // amount_charged: u64
+        8 + // charged_at: i64
+        1 + // bump: u8
+        64; // padding: [u8; 64]
+}
+
+/// This is a unique global program configuration managed by an admin that
+/// defines the protocol fees and potentially more.
+#[account]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub max_policies_per_user: u32,
+    pub emergency_pause: bool,
+    pub bump: u8,
+    /// Mint in which self-registering gateways post their bond, default disables registration
+    pub gateway_bond_mint: Pubkey,
+    pub gateway_bond_amount: u64,
+    /// How long a bond stays locked after a gateway deregisters
+    pub gateway_bond_cooldown_seconds: i64,
+    /// Paid out of a finished policy's rent to whoever closes it
+    pub close_policy_bounty_lamports: u64,
+    /// May turn on the emergency pause, but not lift it
+    pub pauser: Pubkey,
+    /// May change fees and other economic parameters
+    pub fee_manager: Pubkey,
+    /// May create, approve, delete and slash gateways
+    pub gateway_approver: Pubkey,
+    /// Maintains the denylist
+    pub compliance: Pubkey,
+    /// How long a queued config change waits before it can be applied
+    pub timelock_seconds: i64,
+    /// Self-registered gateways whose bond has not been withdrawn, the bond
+    /// mint can only change while there are none. Configs deployed before it
+    /// was tracked do not count their existing gateways.
+    pub bonded_gateways: u32,
+    pub padding: [u8; 60],
+}
+
+impl ProgramConfig {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // admin: Pubkey
+        32 + // fee_recipient: Pubkey
+        2 + // protocol_fee_bps: u16
+        4 + // max_policies_per_user: u32
+        1 + // emergency_pause: bool
+        1 + // bump: u8
+        32 + // gateway_bond_mint: Pubkey
+        8 + // gateway_bond_amount: u64
+        8 + // gateway_bond_cooldown_seconds: i64
+        8 + // close_policy_bounty_lamports: u64
+        32 + // pauser: Pubkey
+        32 + // fee_manager: Pubkey
+        32 + // gateway_approver: Pubkey
+        32 + // compliance: Pubkey
+        8 + // timelock_seconds: i64
+        4 + // bonded_gateways: u32
+        60; // padding: [u8; 60]
+
+    /// Delay before a queued change applies. Configs deployed before the
+    /// timelock existed read 0 and wait the default.
+    pub fn timelock(&self) -> i64 {
+        if self.timelock_seconds == 0 {
+            crate::constants::DEFAULT_CONFIG_TIMELOCK_SECONDS
+        } else {
+            self.timelock_seconds
+        }
+    }
+
+    /// The key holding `role`. Roles that were never assigned are held by the admin.
+    pub fn role_holder(&self, role: ConfigRole) -> Pubkey {
+        let holder = match role {
+            ConfigRole::Admin => self.admin,
+            ConfigRole::Pauser => self.pauser,
+            ConfigRole::FeeManager => self.fee_manager,
+            ConfigRole::GatewayApprover => self.gateway_approver,
+            ConfigRole::Compliance => self.compliance,
+        };
+        if holder == Pubkey::default() {
+            self.admin
+        } else {
+            holder
+        }
+    }
+
+    pub fn has_role(&self, key: &Pubkey, role: ConfigRole) -> bool {
+        self.role_holder(role) == *key
+    }
+}
+
+/// A change to the program config that has to wait out the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum ConfigChange {
+    ProtocolFeeBps(u16),
+    FeeRecipient(Pubkey),
+    MaxPoliciesPerUser(u32),
+    TimelockSeconds(i64),
+}
+
+impl ConfigChange {
+    pub const SIZE: usize = 1 + 32; // discriminator + largest variant
+
+    /// Identifies the changed field, at most one change per field is pending
+    pub fn kind(&self) -> u8 {
+        match self {
+            ConfigChange::ProtocolFeeBps(_) => 0,
+            ConfigChange::FeeRecipient(_) => 1,
+            ConfigChange::MaxPoliciesPerUser(_) => 2,
+            ConfigChange::TimelockSeconds(_) => 3,
+        }
+    }
+
+    /// The role that may queue or cancel this change
+    pub fn required_role(&self) -> ConfigRole {
+        match self {
+            ConfigChange::ProtocolFeeBps(_) | ConfigChange::FeeRecipient(_) => {
+                ConfigRole::FeeManager
+            }
+            ConfigChange::MaxPoliciesPerUser(_) | ConfigChange::TimelockSeconds(_) => {
+                ConfigRole::Admin
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ConfigChange::ProtocolFeeBps(fee_bps) => {
+                require!(
+                    *fee_bps <= 10000,
+                    crate::error::RecurringPaymentsError::InvalidFeeBps
+                );
+            }
+            ConfigChange::FeeRecipient(fee_recipient) => {
+                require!(
+                    *fee_recipient != Pubkey::default(),
+                    crate::error::RecurringPaymentsError::InvalidAmount
+                );
+            }
+            ConfigChange::MaxPoliciesPerUser(max_policies) => {
+                require!(
+                    *max_policies > 0,
+                    crate::error::RecurringPaymentsError::InvalidAmount
+                );
+            }
+            ConfigChange::TimelockSeconds(timelock_seconds) => {
+                require!(
+                    (crate::constants::MIN_CONFIG_TIMELOCK_SECONDS
+                        ..=crate::constants::MAX_CONFIG_TIMELOCK_SECONDS)
+                        .contains(timelock_seconds),
+                    crate::error::RecurringPaymentsError::InvalidInterval
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, config: &mut ProgramConfig) {
+        match self {
+            ConfigChange::ProtocolFeeBps(fee_bps) => config.protocol_fee_bps = *fee_bps,
+            ConfigChange::FeeRecipient(fee_recipient) => config.fee_recipient = *fee_recipient,
+            ConfigChange::MaxPoliciesPerUser(max_policies) => {
+                config.max_policies_per_user = *max_policies
+            }
+            ConfigChange::TimelockSeconds(timelock_seconds) => {
+                config.timelock_seconds = *timelock_seconds
+            }
+        }
+    }
+}
+
+/// A queued config change, readable by gateways and merchants before it applies
+#[account]
+pub struct PendingConfigChange {
+    pub change: ConfigChange,
+    pub queued_by: Pubkey,
+    pub queued_at: i64,
+    /// When the change may be applied, it expires
+    /// `CONFIG_CHANGE_EXPIRY_SECONDS` later
+    pub executable_at: i64,
+    pub bump: u8,
+    pub padding: [u8; 32],
+}
+
+impl PendingConfigChange {
+    pub const SIZE: usize = 8 + // discriminator
+        ConfigChange::SIZE + // change: ConfigChange
+        32 + // queued_by: Pubkey
+        8 + // queued_at: i64
+        8 + // executable_at: i64
+        1 + // bump: u8
+        32; // padding: [u8; 32]
+
+    /// Whether the change can still be applied at `now`. It lapses once it
+    /// expires or when whoever queued it no longer holds the required role.
+    pub fn is_live(&self, config: &ProgramConfig, now: i64) -> bool {
+        now < self
+            .executable_at
+            .saturating_add(crate::constants::CONFIG_CHANGE_EXPIRY_SECONDS)
+            && config.has_role(&self.queued_by, self.change.required_role())
+    }
+}
+
+/// A privileged role in the program config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ConfigRole {
+    /// Assigns roles and lifts the emergency pause
+    Admin,
+    Pauser,
+    FeeManager,
+    GatewayApprover,
+    Compliance,
 }
 
 /// An event that is thrown when a payment takes place
@@ -252,6 +1390,8 @@ pub struct PaymentRecord {
     pub timestamp: i64,
     pub memo: [u8; 64],
     pub record_id: u32,
+    /// Invoice the executor referenced for this payment, zero if none
+    pub invoice_reference: [u8; 32],
 }
 
 /// An event that is thrown when the program is initialized
@@ -290,6 +1430,8 @@ pub struct PaymentPolicyCreated {
     pub policy_id: u32,
     pub policy_type: PolicyType,
     pub memo: [u8; 64],
+    pub gateway_fee_bps: u16,
+    pub protocol_fee_bps: u16,
 }
 
 /// An event that is thrown when a gateway signer is changed
@@ -330,4 +1472,730 @@ pub struct PaymentGatewayDeleted {
     pub gateway: Pubkey,
     pub authority: Pubkey,
     pub name: [u8; 32],
+    pub active_policies_count: u32,
+    pub successor: Option<Pubkey>,
+}
+
+/// An event that is thrown when existing policies are added to a gateway's policy count
+#[event]
+pub struct GatewayPolicyCountBackfilled {
+    pub gateway: Pubkey,
+    pub counted: u32,
+    pub active_policies_count: u32,
+    pub policy_count_complete: bool,
+}
+
+/// An event that is thrown when a payer accepts the current fee rates for a policy
+#[event]
+pub struct PaymentPolicyFeesAccepted {
+    pub payment_policy: Pubkey,
+    pub old_gateway_fee_bps: u16,
+    pub new_gateway_fee_bps: u16,
+    pub old_protocol_fee_bps: u16,
+    pub new_protocol_fee_bps: u16,
+}
+
+/// An event that is thrown when a payment policy is moved to another gateway
+#[event]
+pub struct PaymentPolicyGatewayMigrated {
+    pub payment_policy: Pubkey,
+    pub old_gateway: Pubkey,
+    pub new_gateway: Pubkey,
+}
+
+/// An event that is thrown when the gateway bond requirements are changed
+#[event]
+pub struct GatewayBondConfigUpdated {
+    pub bond_mint: Pubkey,
+    pub bond_amount: u64,
+    pub cooldown_seconds: i64,
+}
+
+/// An event that is thrown when a gateway registers itself and posts a bond
+#[event]
+pub struct GatewayRegistered {
+    pub gateway: Pubkey,
+    pub authority: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub gateway_fee_bps: u16,
+    pub bond_mint: Pubkey,
+    pub bond_amount: u64,
+    pub name: [u8; 32],
+    pub url: [u8; 64],
+}
+
+/// An event that is thrown when the admin approves a registered gateway
+#[event]
+pub struct GatewayApproved {
+    pub gateway: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// An event that is thrown when the admin slashes a gateway bond
+#[event]
+pub struct GatewayBondSlashed {
+    pub gateway: Pubkey,
+    pub amount: u64,
+    pub remaining_bond: u64,
+    pub is_active: bool,
+}
+
+/// An event that is thrown when a gateway adds to its bond
+#[event]
+pub struct GatewayBondToppedUp {
+    pub gateway: Pubkey,
+    pub amount: u64,
+    pub bond_amount: u64,
+}
+
+/// An event that is thrown when a gateway deregisters itself
+#[event]
+pub struct GatewayDeregistered {
+    pub gateway: Pubkey,
+    pub authority: Pubkey,
+    pub bond_unlocks_at: i64,
+}
+
+/// An event that is thrown when a deregistered gateway withdraws its bond
+#[event]
+pub struct GatewayBondWithdrawn {
+    pub gateway: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+/// An event that is thrown when a gateway signer is added or updated
+#[event]
+pub struct GatewaySignerAdded {
+    pub gateway: Pubkey,
+    pub signer: Pubkey,
+    pub role: GatewaySignerRole,
+    pub expires_at: i64,
+}
+
+/// An event that is thrown when a gateway signer is removed
+#[event]
+pub struct GatewaySignerRemoved {
+    pub gateway: Pubkey,
+    pub signer: Pubkey,
+}
+
+/// An event that is thrown when a gateway reports a failed payment attempt
+#[event]
+pub struct PaymentFailed {
+    pub payment_policy: Pubkey,
+    pub gateway: Pubkey,
+    pub reporter: Pubkey,
+    pub reason_code: u16,
+    pub timestamp: i64,
+}
+
+/// An event that is thrown when a gateway changes its keeper settings
+#[event]
+pub struct GatewayKeeperSettingsChanged {
+    pub gateway: Pubkey,
+    pub keeper_grace_seconds: i64,
+    pub keeper_bounty_bps: u16,
+}
+
+/// An event that is thrown when a keeper is paid for executing an overdue payment
+#[event]
+pub struct KeeperBountyPaid {
+    pub payment_policy: Pubkey,
+    pub keeper: Pubkey,
+    pub amount: u64,
+}
+
+/// An event that is thrown when a SOL fee is not charged because its destination
+/// would not be rent exempt after receiving it
+#[event]
+pub struct SolFeeSkipped {
+    pub payment_policy: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// An event that is thrown when a recipient refunds a payment
+#[event]
+pub struct PaymentRefunded {
+    pub payment_policy: Pubkey,
+    pub recipient: Pubkey,
+    pub refunded_by: Pubkey,
+    pub record_id: u32,
+    pub amount: u64,
+    pub total_refunded: u64,
+    pub timestamp: i64,
+}
+
+/// An event that is thrown when a recipient changes the escrow hold of a policy
+#[event]
+pub struct PaymentPolicyHoldChanged {
+    pub payment_policy: Pubkey,
+    pub hold_seconds: u32,
+}
+
+/// An event that is thrown when a payment is moved into escrow
+#[event]
+pub struct PaymentEscrowed {
+    pub payment_policy: Pubkey,
+    pub escrow: Pubkey,
+    pub record_id: u32,
+    pub amount: u64,
+    pub release_at: i64,
+}
+
+/// An event that is thrown when a payer disputes an escrowed payment
+#[event]
+pub struct EscrowDisputed {
+    pub payment_policy: Pubkey,
+    pub escrow: Pubkey,
+    pub record_id: u32,
+}
+
+/// An event that is thrown when an escrowed payment is paid out
+#[event]
+pub struct EscrowSettled {
+    pub payment_policy: Pubkey,
+    pub escrow: Pubkey,
+    pub record_id: u32,
+    pub amount: u64,
+    pub to_recipient: bool,
+}
+
+/// An event that is thrown when a user switches to a prepaid vault
+#[event]
+pub struct UserPaymentVaultCreated {
+    pub user_payment: Pubkey,
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+}
+
+/// An event that is thrown when tokens are deposited into a prepaid vault
+#[event]
+pub struct VaultDeposited {
+    pub user_payment: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+/// An event that is thrown when tokens are withdrawn from a prepaid vault
+#[event]
+pub struct VaultWithdrawn {
+    pub user_payment: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+/// An event that is thrown when a payer closes their prepaid vault and returns to delegation
+#[event]
+pub struct UserPaymentVaultClosed {
+    pub user_payment: Pubkey,
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub withdrawn: u64,
+}
+
+/// An event that is thrown when a prepaid vault cannot cover the next due payment
+#[event]
+pub struct VaultBalanceLow {
+    pub user_payment: Pubkey,
+    pub payment_policy: Pubkey,
+    pub balance: u64,
+    pub next_payment_amount: u64,
+    pub next_payment_due: i64,
+}
+
+/// An event that is thrown when a payer stops a stream
+#[event]
+pub struct StreamStopped {
+    pub payment_policy: Pubkey,
+    pub end: i64,
+    pub claimable: u64,
+}
+
+/// An event that is thrown when a gateway signer reports usage for a metered period
+#[event]
+pub struct UsageReported {
+    pub payment_policy: Pubkey,
+    pub usage_report: Pubkey,
+    pub record_id: u32,
+    pub period_end: i64,
+    pub units: u64,
+    pub reporter: Pubkey,
+}
+
+/// An event that is thrown when the usage of a metered period is charged
+#[event]
+pub struct UsageCharged {
+    pub payment_policy: Pubkey,
+    pub usage_report: Pubkey,
+    pub record_id: u32,
+    pub units: u64,
+    pub amount: u64,
+}
+
+/// An event that is thrown when a payer changes their spending limit
+#[event]
+pub struct SpendingLimitSet {
+    pub user_payment: Pubkey,
+    pub limit: u64,
+    pub period_seconds: i64,
+}
+
+/// An event that is thrown when a payer caps what a recipient can be paid
+#[event]
+pub struct RecipientSpendingCapSet {
+    pub user_payment: Pubkey,
+    pub recipient: Pubkey,
+    pub limit: u64,
+    pub period_seconds: i64,
+}
+
+/// An event that is thrown when a payer removes a recipient cap
+#[event]
+pub struct RecipientSpendingCapRemoved {
+    pub user_payment: Pubkey,
+    pub recipient: Pubkey,
+}
+
+/// An event that is thrown when a payer changes the token account payments are drawn from
+#[event]
+pub struct UserPaymentTokenAccountChanged {
+    pub user_payment: Pubkey,
+    pub old_token_account: Pubkey,
+    pub new_token_account: Pubkey,
+}
+
+/// An event that is thrown when a payer pauses or resumes all of their payments
+#[event]
+pub struct UserPaymentActiveChanged {
+    pub user_payment: Pubkey,
+    pub is_active: bool,
+}
+
+/// An event that is thrown when a payer closes their user payment account
+#[event]
+pub struct UserPaymentClosed {
+    pub user_payment: Pubkey,
+    pub owner: Pubkey,
+    pub token_mint: Pubkey,
+    pub delegate_revoked: bool,
+}
+
+/// An event that is thrown when the bounty for closing finished policies changes
+#[event]
+pub struct ClosePolicyBountyUpdated {
+    pub bounty_lamports: u64,
+}
+
+/// An event that is thrown when a finished policy is closed by the crank
+#[event]
+pub struct FinishedPolicyClosed {
+    pub payment_policy: Pubkey,
+    pub owner: Pubkey,
+    pub policy_id: u32,
+    pub closer: Pubkey,
+    pub bounty_lamports: u64,
+}
+
+/// An event that is thrown when payer and recipient change the term of a policy
+#[event]
+pub struct PaymentPolicyTermExtended {
+    pub payment_policy: Pubkey,
+    pub ends_at: i64,
+    pub max_renewals: Option<u32>,
+}
+
+/// An event that is thrown when a payer pauses a policy until a given time
+#[event]
+pub struct PaymentPolicyPausedUntil {
+    pub payment_policy: Pubkey,
+    pub paused_until: i64,
+}
+
+/// An event that is thrown when a scheduled pause ends
+#[event]
+pub struct PaymentPolicyResumed {
+    pub payment_policy: Pubkey,
+    pub next_payment_due: i64,
+}
+
+/// An event that is thrown when a recipient limits how long a policy can be paused
+#[event]
+pub struct PaymentPolicyMaxPauseChanged {
+    pub payment_policy: Pubkey,
+    pub max_pause_seconds: u32,
+}
+
+/// An event that is thrown when a recipient suspends, cancels or reinstates a policy
+#[event]
+pub struct RecipientPolicyStatusChanged {
+    pub payment_policy: Pubkey,
+    pub recipient: Pubkey,
+    pub old_status: PaymentStatus,
+    pub new_status: PaymentStatus,
+    pub reason_code: u16,
+}
+
+/// An event that is thrown when a recipient changes their settings
+#[event]
+pub struct RecipientSettingsUpdated {
+    pub recipient: Pubkey,
+    pub require_acceptance: bool,
+}
+
+/// An event that is thrown when a recipient accepts a pending policy
+#[event]
+pub struct PaymentPolicyAccepted {
+    pub payment_policy: Pubkey,
+    pub recipient: Pubkey,
+}
+
+/// An event that is thrown when a merchant profile is registered or changed
+#[event]
+pub struct MerchantUpdated {
+    pub merchant: Pubkey,
+    pub authority: Pubkey,
+    pub name: [u8; 32],
+    pub url: [u8; 64],
+    pub logo_hash: [u8; 32],
+    pub support_contact: [u8; 64],
+}
+
+/// An event that is thrown when a merchant's settlement account for a mint is set or removed
+#[event]
+pub struct MerchantSettlementAccountChanged {
+    pub merchant: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Option<Pubkey>,
+}
+
+/// An event that is thrown when a gateway verifies a merchant or revokes its verification
+#[event]
+pub struct MerchantVerificationChanged {
+    pub merchant: Pubkey,
+    pub gateway: Pubkey,
+    pub verified: bool,
+}
+
+/// An event that is thrown when an address is added to the denylist
+#[event]
+pub struct AddressDenylisted {
+    pub address: Pubkey,
+    pub reason_code: u16,
+}
+
+/// An event that is thrown when an address is removed from the denylist
+#[event]
+pub struct AddressRemovedFromDenylist {
+    pub address: Pubkey,
+}
+
+/// An event that is thrown when a config role is assigned to another key
+#[event]
+pub struct ConfigRoleChanged {
+    pub role: ConfigRole,
+    pub old_holder: Pubkey,
+    pub new_holder: Pubkey,
+}
+
+/// An event that is thrown when the emergency pause is turned on or off
+#[event]
+pub struct EmergencyPauseChanged {
+    pub paused: bool,
+    pub changed_by: Pubkey,
+}
+
+/// An event that is thrown when a config change is queued
+#[event]
+pub struct ConfigChangeQueued {
+    pub change: ConfigChange,
+    pub queued_by: Pubkey,
+    pub executable_at: i64,
+}
+
+/// An event that is thrown when a queued config change is applied
+#[event]
+pub struct ConfigChangeExecuted {
+    pub change: ConfigChange,
+}
+
+/// An event that is thrown when a queued config change is cancelled
+#[event]
+pub struct ConfigChangeCancelled {
+    pub change: ConfigChange,
+    pub cancelled_by: Pubkey,
+}
+
+/// An account of `size` bytes with all fields zeroed, as fields carved from
+/// padding read on accounts created before they existed
+#[cfg(test)]
+pub(crate) fn zeroed_account<T: AnchorDeserialize>(size: usize) -> T {
+    let data = vec![0u8; size - 8];
+    T::deserialize(&mut &data[..]).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_policy() -> PaymentPolicy {
+        zeroed_account(PaymentPolicy::SIZE)
+    }
+
+    #[test]
+    fn legacy_policy_pays_live_fee_rates() {
+        let policy = legacy_policy();
+        assert!(!policy.fees_snapshotted);
+        assert_eq!(policy.fee_rates(250, 100), (250, 100));
+    }
+
+    #[test]
+    fn refunds_are_capped_per_payment() {
+        let mut policy = legacy_policy();
+        policy.total_paid = 300;
+        policy.push_receipt(1, 100);
+        policy.push_receipt(2, 200);
+
+        policy.book_refund(1, 60).unwrap();
+        assert_eq!(
+            policy.book_refund(1, 50).unwrap_err(),
+            crate::error::RecurringPaymentsError::RefundExceedsPaid.into()
+        );
+        policy.book_refund(1, 40).unwrap();
+        policy.book_refund(2, 200).unwrap();
+        assert_eq!(policy.total_refunded, 300);
+        assert_eq!(
+            policy.book_refund(3, 1).unwrap_err(),
+            crate::error::RecurringPaymentsError::RefundWindowClosed.into()
+        );
+    }
+
+    #[test]
+    fn disputes_can_not_refund_a_payment_twice() {
+        let mut policy = legacy_policy();
+        policy.total_paid = 300;
+        policy.push_receipt(1, 100);
+        policy.push_receipt(2, 200);
+
+        // The recipient already refunded most of payment 1
+        policy.book_refund(1, 60).unwrap();
+        assert_eq!(
+            policy.book_dispute_refund(1, 97).unwrap_err(),
+            crate::error::RecurringPaymentsError::RefundExceedsPaid.into()
+        );
+        policy.book_dispute_refund(2, 194).unwrap();
+        assert_eq!(policy.total_refunded, 254);
+
+        // Older payments only count against the total paid
+        for record_id in 3..=6 {
+            policy.push_receipt(record_id, 0);
+        }
+        policy.book_dispute_refund(1, 40).unwrap();
+        assert_eq!(
+            policy.book_dispute_refund(2, 7).unwrap_err(),
+            crate::error::RecurringPaymentsError::RefundExceedsPaid.into()
+        );
+    }
+
+    #[test]
+    fn only_recent_payments_can_be_refunded() {
+        let mut policy = legacy_policy();
+        policy.total_paid = 500;
+        for record_id in 1..=5 {
+            policy.push_receipt(record_id, 100);
+        }
+
+        assert!(policy.receipt_mut(1).is_none());
+        assert!(policy.receipt_mut(0).is_none());
+        policy.book_refund(2, 100).unwrap();
+        assert_eq!(policy.recent_payments[0].record_id, 5);
+    }
+
+    #[test]
+    fn snapshotted_policy_pays_lower_fee_rates() {
+        let mut policy = legacy_policy();
+        policy.snapshot_fees(250, 100);
+        assert_eq!(policy.fee_rates(300, 50), (250, 50));
+        assert_eq!(policy.fee_rates(0, 0), (0, 0));
+    }
+
+    #[test]
+    fn wrapped_sol_vault_is_a_token_vault() {
+        let mut user_payment: UserPayment = zeroed_account(UserPayment::SIZE);
+        user_payment.token_mint = anchor_spl::token::spl_token::native_mint::ID;
+        user_payment.vault = Pubkey::new_unique();
+        user_payment.funding_mode = FundingMode::TokenVault;
+
+        assert!(user_payment.has_vault());
+        assert!(!user_payment.is_native_sol());
+    }
+
+    #[test]
+    fn every_policy_type_has_an_upcoming_payment() {
+        let stream = PolicyType::Stream {
+            rate_per_second: 10,
+            start: 0,
+            end: 100,
+            withdrawn: 400,
+            padding: [0; 96],
+        };
+        assert_eq!(stream.upcoming_payment(0).unwrap(), Some((600, 100)));
+
+        let metered = PolicyType::Metered {
+            unit_price: 5,
+            max_units_per_period: 20,
+            period: PaymentFrequency::Daily,
+            next_payment_due: 50,
+            padding: [0; 95],
+        };
+        assert_eq!(metered.upcoming_payment(0).unwrap(), Some((100, 50)));
+
+        let fiat = PolicyType::FiatSubscription {
+            fiat_amount: 1_000_000,
+            price_feed: Pubkey::new_unique(),
+            payment_frequency: PaymentFrequency::Daily,
+            next_payment_due: 70,
+            max_slippage_bps: 100,
+            feed_id: [7; 32],
+            padding: [0; 37],
+        };
+        assert_eq!(fiat.upcoming_payment(42).unwrap(), Some((42, 70)));
+    }
+
+    #[test]
+    fn spending_limit_rolls_over_a_whole_period() {
+        let day = 24 * 60 * 60;
+        let mut limit = SpendingLimit::new(1_000, day).unwrap();
+        let start = 100 * day;
+
+        // Spending the limit at the end of one day blocks the start of the next
+        assert!(limit.try_charge(1_000, start + day - 1).unwrap());
+        assert!(!limit.try_charge(1, start + day + 1).unwrap());
+        assert!(!limit.try_charge(1, start + 2 * day - 2).unwrap());
+
+        // A day after the charge, its bucket has left the window
+        assert!(limit.try_charge(1_000, start + 2 * day + day / 7).unwrap());
+        assert!(!limit.try_charge(1, start + 2 * day + day / 7).unwrap());
+    }
+
+    #[test]
+    fn spending_limit_keeps_recent_charges() {
+        let mut limit = SpendingLimit::new(300, 700).unwrap();
+        assert!(limit.try_charge(100, 0).unwrap());
+        assert!(limit.try_charge(100, 350).unwrap());
+        assert!(limit.try_charge(100, 690).unwrap());
+        assert!(!limit.try_charge(1, 750).unwrap());
+
+        // The first charge expires, the later ones still count
+        assert!(limit.try_charge(100, 800).unwrap());
+        assert!(!limit.try_charge(1, 800).unwrap());
+    }
+
+    #[test]
+    fn user_payment_size_is_unchanged() {
+        assert_eq!(UserPayment::SIZE, 382);
+    }
+
+    /// Subscription as laid out before it had an end date
+    #[derive(AnchorSerialize)]
+    enum LegacyPolicyType {
+        Subscription {
+            amount: u64,
+            auto_renew: bool,
+            max_renewals: Option<u32>,
+            payment_frequency: PaymentFrequency,
+            next_payment_due: i64,
+            padding: [u8; 97],
+        },
+    }
+
+    #[test]
+    fn legacy_subscription_is_open_ended() {
+        let legacy = LegacyPolicyType::Subscription {
+            amount: 500,
+            auto_renew: true,
+            max_renewals: Some(12),
+            payment_frequency: PaymentFrequency::Monthly,
+            next_payment_due: 1_000,
+            padding: [0; 97],
+        };
+        let mut data = legacy.try_to_vec().unwrap();
+        data.resize(PolicyType::TOTAL_SIZE, 0);
+
+        let policy_type = PolicyType::deserialize(&mut &data[..]).unwrap();
+        let PolicyType::Subscription {
+            amount,
+            max_renewals,
+            next_payment_due,
+            ends_at,
+            ..
+        } = policy_type
+        else {
+            panic!("expected a subscription");
+        };
+        assert_eq!(
+            (amount, max_renewals, next_payment_due),
+            (500, Some(12), 1_000)
+        );
+        assert_eq!(ends_at, 0);
+        assert!(!policy_type.is_finished(3, 2_000).unwrap());
+        assert_eq!(policy_type.due_payment(2_000).unwrap(), (500, 1_000));
+    }
+
+    #[test]
+    fn legacy_config_waits_the_default_timelock() {
+        let mut config: ProgramConfig = zeroed_account(ProgramConfig::SIZE);
+        assert_eq!(
+            config.timelock(),
+            crate::constants::DEFAULT_CONFIG_TIMELOCK_SECONDS
+        );
+        config.timelock_seconds = 3600;
+        assert_eq!(config.timelock(), 3600);
+    }
+
+    #[test]
+    fn timelock_changes_are_bounded() {
+        assert!(ConfigChange::TimelockSeconds(0).validate().is_err());
+        assert!(ConfigChange::TimelockSeconds(59 * 60).validate().is_err());
+        assert!(ConfigChange::TimelockSeconds(60 * 60).validate().is_ok());
+        assert!(ConfigChange::TimelockSeconds(30 * 24 * 60 * 60)
+            .validate()
+            .is_ok());
+        assert!(ConfigChange::TimelockSeconds(31 * 24 * 60 * 60)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn pending_config_changes_lapse() {
+        let mut config: ProgramConfig = zeroed_account(ProgramConfig::SIZE);
+        config.admin = Pubkey::new_unique();
+        let mut pending: PendingConfigChange = zeroed_account(PendingConfigChange::SIZE);
+        pending.change = ConfigChange::ProtocolFeeBps(50);
+        pending.queued_by = config.admin;
+        pending.executable_at = 1_000;
+
+        assert!(pending.is_live(&config, 1_000));
+        let expires_at = 1_000 + crate::constants::CONFIG_CHANGE_EXPIRY_SECONDS;
+        assert!(pending.is_live(&config, expires_at - 1));
+        assert!(!pending.is_live(&config, expires_at));
+
+        // Handing the fee manager role to someone else drops the admin's change
+        config.fee_manager = Pubkey::new_unique();
+        assert!(!pending.is_live(&config, 1_000));
+    }
+
+    #[test]
+    fn policy_ids_are_not_reused_after_closing() {
+        let mut user_payment: UserPayment = zeroed_account(UserPayment::SIZE);
+        user_payment.active_policies_count = 2;
+        assert_eq!(user_payment.next_policy_id(), 3);
+
+        user_payment.last_policy_id = 3;
+        user_payment.active_policies_count = 3;
+        // Closing policy 1 must not hand out id 3 again
+        user_payment.active_policies_count = 2;
+        assert_eq!(user_payment.next_policy_id(), 4);
+    }
 }
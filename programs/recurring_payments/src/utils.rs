@@ -1,6 +1,211 @@
-use crate::{error::RecurringPaymentsError, PaymentFrequency};
+use crate::{
+    error::RecurringPaymentsError, GatewaySignerRole, GatewaySigners, PaymentFrequency,
+    PaymentGateway, PaymentPolicy, PaymentPolicyGatewayMigrated, PaymentPolicyResumed,
+    PaymentStatus, RecipientSettings, RecipientSpendingCap,
+};
 use anchor_lang::prelude::*;
 
+/// Check if a key may act for a gateway with the given role. The gateway's
+/// primary signer holds every role.
+pub fn is_gateway_signer(
+    gateway: &PaymentGateway,
+    gateway_signers: Option<&GatewaySigners>,
+    key: &Pubkey,
+    role: GatewaySignerRole,
+    now: i64,
+) -> bool {
+    gateway.signer == *key
+        || gateway_signers.is_some_and(|signers| signers.authorizes(key, role, now))
+}
+
+/// Whether an account stays rent exempt after receiving `amount` lamports. The
+/// runtime rejects transfers that leave an account with a balance below that.
+pub fn stays_rent_exempt(destination: &AccountInfo, amount: u64, rent: &Rent) -> bool {
+    amount == 0
+        || destination.lamports().saturating_add(amount)
+            >= rent.minimum_balance(destination.data_len())
+}
+
+/// Check if a key may refund payments of a policy: its recipient, or an
+/// operator key of its gateway
+pub fn is_refunder(
+    key: &Pubkey,
+    payment_policy: &PaymentPolicy,
+    gateway: Option<&PaymentGateway>,
+    gateway_signers: Option<&GatewaySigners>,
+    now: i64,
+) -> bool {
+    payment_policy.recipient == *key
+        || gateway.is_some_and(|gateway| {
+            is_gateway_signer(
+                gateway,
+                gateway_signers,
+                key,
+                GatewaySignerRole::Operator,
+                now,
+            )
+        })
+}
+
+/// Decrease the policy count of a gateway. The gateway may already have been
+/// deleted, in which case there is nothing left to update. Policies the
+/// gateway never counted leave the count as is.
+pub fn release_gateway_policy(
+    gateway_info: &AccountInfo,
+    payment_policy: &PaymentPolicy,
+) -> Result<()> {
+    if !payment_policy.counted_by_gateway
+        || gateway_info.owner != &crate::ID
+        || gateway_info.data_is_empty()
+    {
+        return Ok(());
+    }
+
+    let mut data = gateway_info.try_borrow_mut_data()?;
+    let mut gateway = PaymentGateway::try_deserialize(&mut &data[..])?;
+    gateway.active_policies_count = gateway
+        .active_policies_count
+        .checked_sub(1)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+    gateway.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// Hand a policy passed as a remaining account over to another gateway, on
+/// behalf of both gateway authorities. Returns whether the old gateway
+/// counted the policy.
+pub fn move_policy_to_gateway(
+    policy_info: &AccountInfo,
+    old_gateway: &Account<PaymentGateway>,
+    new_gateway: &Pubkey,
+    protocol_fee_bps: u16,
+    now: i64,
+) -> Result<bool> {
+    require!(
+        policy_info.owner == &crate::ID && policy_info.is_writable,
+        RecurringPaymentsError::PolicyNotFound
+    );
+
+    let mut data = policy_info.try_borrow_mut_data()?;
+    let mut payment_policy = PaymentPolicy::try_deserialize(&mut &data[..])?;
+    require!(
+        payment_policy.gateway == old_gateway.key(),
+        RecurringPaymentsError::InvalidGateway
+    );
+
+    // The payer did not sign, so the agreed gateway fee rate stays in place
+    // and execution keeps charging the lower of both rates. Policies
+    // without agreed rates keep the rates they pay today.
+    if !payment_policy.fees_snapshotted {
+        payment_policy.snapshot_fees(old_gateway.gateway_fee_bps, protocol_fee_bps);
+    }
+    let was_counted = payment_policy.counted_by_gateway;
+    payment_policy.gateway = *new_gateway;
+    payment_policy.counted_by_gateway = true;
+    payment_policy.updated_at = now;
+    payment_policy.try_serialize(&mut &mut data[..])?;
+
+    emit!(PaymentPolicyGatewayMigrated {
+        payment_policy: policy_info.key(),
+        old_gateway: old_gateway.key(),
+        new_gateway: *new_gateway,
+    });
+
+    Ok(was_counted)
+}
+
+/// Close the signer set of a gateway that is being closed, so a gateway
+/// created later at the same address does not inherit it. Gateways without a
+/// signer set have nothing to close.
+pub fn close_gateway_signers<'info>(
+    signers_info: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    if signers_info.owner != &crate::ID || signers_info.data_is_empty() {
+        return Ok(());
+    }
+
+    let lamports = signers_info.lamports();
+    **signers_info.try_borrow_mut_lamports()? = 0;
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(RecurringPaymentsError::ArithmeticOverflow)?;
+    signers_info.assign(&anchor_lang::system_program::ID);
+    signers_info.resize(0)?;
+
+    Ok(())
+}
+
+/// Charge a payment against the payer's cap for its recipient. The cap
+/// account is optional, without one there is nothing to enforce.
+pub fn charge_recipient_cap(cap_info: &AccountInfo, amount: u64, now: i64) -> Result<()> {
+    if cap_info.owner != &crate::ID || cap_info.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut data = cap_info.try_borrow_mut_data()?;
+    let mut cap = RecipientSpendingCap::try_deserialize(&mut &data[..])?;
+    require!(
+        cap.spending_limit.try_charge(amount, now)?,
+        RecurringPaymentsError::RecipientCapExceeded
+    );
+    cap.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// Fail with `error` if the denylist entry exists. Entries are passed at
+/// their PDA address, so the check cannot be skipped by omitting them.
+pub fn require_not_denylisted(
+    entry_info: &AccountInfo,
+    error: RecurringPaymentsError,
+) -> Result<()> {
+    if entry_info.owner == &crate::ID && !entry_info.data_is_empty() {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+/// Whether a recipient requires accepting new policies. Recipients without
+/// settings accept every policy.
+pub fn recipient_requires_acceptance(settings_info: &AccountInfo) -> Result<bool> {
+    if settings_info.owner != &crate::ID || settings_info.data_is_empty() {
+        return Ok(false);
+    }
+
+    let data = settings_info.try_borrow_data()?;
+    let settings = RecipientSettings::try_deserialize(&mut &data[..])?;
+    Ok(settings.require_acceptance)
+}
+
+/// Resume a scheduled pause that has run out. The periods in between are
+/// skipped.
+pub fn resume_if_due(payment_policy: &mut Account<PaymentPolicy>, now: i64) -> Result<()> {
+    if payment_policy.status != PaymentStatus::Paused
+        || payment_policy.paused_until == 0
+        || now < payment_policy.paused_until
+    {
+        return Ok(());
+    }
+
+    let paused_until = payment_policy.paused_until;
+    payment_policy
+        .policy_type
+        .skip_periods_until(paused_until)?;
+    payment_policy.status = PaymentStatus::Active;
+    payment_policy.paused_until = 0;
+    payment_policy.updated_at = now;
+
+    emit!(PaymentPolicyResumed {
+        payment_policy: payment_policy.key(),
+        next_payment_due: payment_policy.policy_type.period_end(),
+    });
+
+    Ok(())
+}
+
 /// Calculate the next payment due date based on payment frequency
 pub fn calculate_next_payment_due(
     current_due: i64,
@@ -4,7 +4,11 @@ import { Connection, PublicKey, Keypair } from "@solana/web3.js";
 import * as anchor from "@coral-xyz/anchor";
 import * as cron from "node-cron";
 import * as fs from "fs";
-import { getGatewayPda, RecurringPaymentsSDK } from "@tributary-so/sdk";
+import {
+  getGatewayPda,
+  getNextPaymentDue,
+  RecurringPaymentsSDK,
+} from "@tributary-so/sdk";
 import { exit } from "process";
 
 interface SchedulerConfig {
@@ -12,6 +16,7 @@ interface SchedulerConfig {
   gatewayKeypairPath?: string;
   privateKey?: string;
   cronSchedule?: string; // Default: every hour
+  keeperMode?: boolean; // Also execute overdue payments of gateways that allow keepers
 }
 
 class PaymentScheduler {
@@ -73,13 +78,25 @@ class PaymentScheduler {
       );
 
       const currentTime = Math.floor(Date.now() / 1000);
+
+      if (this.config.keeperMode) {
+        const overdue = await this.getKeeperPolicies(gatewayPda, currentTime);
+        console.log(
+          `Found ${overdue.length} overdue payment policies of other gateways`
+        );
+        paymentPolicies.push(...overdue);
+      }
+
       let executedCount = 0;
       let errorCount = 0;
 
       for (const { publicKey: policyPda, account: policy } of paymentPolicies) {
         try {
           // Check if payment is due and policy is active
-          if (this.shouldExecutePayment(policy, currentTime)) {
+          if (
+            this.shouldExecutePayment(policy, currentTime) &&
+            (await this.hasUsageReport(policyPda, policy))
+          ) {
             console.log(
               `Executing payment for policy: ${policyPda.toString()}`
             );
@@ -110,9 +127,39 @@ class PaymentScheduler {
     }
   }
 
+  /**
+   * Policies of other gateways that are overdue by more than their gateway's
+   * keeper grace period, so anyone may execute them for a bounty
+   */
+  private async getKeeperPolicies(ownGateway: PublicKey, currentTime: number) {
+    const gateways = await this.sdk.getAllPaymentGateway();
+    const graceByGateway = new Map<string, number>();
+    for (const { publicKey, account } of gateways) {
+      const grace = account.keeperGraceSeconds.toNumber();
+      if (grace > 0 && !publicKey.equals(ownGateway)) {
+        graceByGateway.set(publicKey.toBase58(), grace);
+      }
+    }
+
+    const policies = await this.sdk.getAllPaymentPolicies();
+    return policies.filter(({ account: policy }) => {
+      const grace = graceByGateway.get(policy.gateway.toBase58());
+      const nextPaymentDue = getNextPaymentDue(policy.policyType);
+      return (
+        grace !== undefined &&
+        nextPaymentDue !== null &&
+        nextPaymentDue + grace <= currentTime
+      );
+    });
+  }
+
   private shouldExecutePayment(policy: any, currentTime: number): boolean {
-    // Check if policy is active
-    if (!policy.status.active) {
+    // Check if policy is active. A scheduled pause that has run out resumes
+    // with the next payment.
+    const pausedUntil = policy.pausedUntil.toNumber();
+    const pauseRunOut =
+      policy.status.paused && pausedUntil > 0 && pausedUntil <= currentTime;
+    if (!policy.status.active && !pauseRunOut) {
       return false;
     }
 
@@ -134,9 +181,52 @@ class PaymentScheduler {
       }
     }
 
+    // The amount of a variable pull is chosen by the merchant's billing system
+    if (policy.policyType.variable) {
+      return false;
+    }
+
+    // Usage based and fiat priced policies are charged per period
+    if (!policy.policyType.subscription && !policy.policyType.stream) {
+      const nextPaymentDue = getNextPaymentDue(policy.policyType);
+      if (nextPaymentDue === null || nextPaymentDue > currentTime) {
+        return false;
+      }
+    }
+
+    // Streams can be claimed whenever something has vested since the last claim
+    if (policy.policyType.stream) {
+      const stream = policy.policyType.stream;
+      const vestedUntil = Math.min(currentTime, stream.end.toNumber());
+      const vested = stream.ratePerSecond.muln(
+        Math.max(vestedUntil - stream.start.toNumber(), 0)
+      );
+      if (vested.lte(stream.withdrawn)) {
+        return false;
+      }
+    }
+
     return true;
   }
 
+  /**
+   * A metered period is charged from the usage the gateway reported for it,
+   * so it can only be executed once that report exists
+   */
+  private async hasUsageReport(
+    policyPda: PublicKey,
+    policy: any
+  ): Promise<boolean> {
+    if (!policy.policyType.metered) {
+      return true;
+    }
+    const { address: usageReport } = this.sdk.getUsageReportPda(
+      policyPda,
+      policy.paymentCount + 1
+    );
+    return (await this.sdk.connection.getAccountInfo(usageReport)) !== null;
+  }
+
   private async executePayment(paymentPolicyPda: PublicKey): Promise<void> {
     try {
       const transaction = new anchor.web3.Transaction();
@@ -211,6 +301,7 @@ if (import.meta.url === `file://${process.argv[1]}`) {
     gatewayKeypairPath: process.env.ANCHOR_WALLET,
     privateKey: process.env.PRIVATE_KEY,
     cronSchedule: process.env.CRON_SCHEDULE || "0 * * * *",
+    keeperMode: process.env.KEEPER_MODE === "true",
   };
 
   const scheduler = new PaymentScheduler(config);
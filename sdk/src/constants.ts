@@ -8,4 +8,16 @@ export const SEEDS = {
   USER_PAYMENT: "user_payment",
   PAYMENT_POLICY: "payment_policy",
   PAYMENTS: "payments",
+  GATEWAY_BOND: "gateway_bond",
+  GATEWAY_SIGNERS: "gateway_signers",
+  ESCROW: "escrow",
+  ESCROW_VAULT: "escrow_vault",
+  USER_VAULT: "user_vault",
+  SOL_VAULT: "sol_vault",
+  USAGE_REPORT: "usage_report",
+  RECIPIENT_CAP: "recipient_cap",
+  DENYLIST: "denylist",
+  RECIPIENT_SETTINGS: "recipient_settings",
+  MERCHANT: "merchant",
+  PENDING_CONFIG_CHANGE: "pending_config_change",
 } as const;
@@ -65,3 +65,152 @@ export function getPaymentsDelegatePda(programId: PublicKey): PdaResult {
   );
   return { address, bump };
 }
+
+export function getGatewayBondPda(
+  gateway: PublicKey,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.GATEWAY_BOND), gateway.toBuffer()],
+    programId
+  );
+  return { address, bump };
+}
+
+export function getGatewaySignersPda(
+  gateway: PublicKey,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.GATEWAY_SIGNERS), gateway.toBuffer()],
+    programId
+  );
+  return { address, bump };
+}
+
+export function getEscrowPda(
+  paymentPolicy: PublicKey,
+  recordId: number,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEEDS.ESCROW),
+      paymentPolicy.toBuffer(),
+      new BN(recordId).toArrayLike(Buffer, "le", 4),
+    ],
+    programId
+  );
+  return { address, bump };
+}
+
+export function getEscrowVaultPda(
+  paymentPolicy: PublicKey,
+  recordId: number,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEEDS.ESCROW_VAULT),
+      paymentPolicy.toBuffer(),
+      new BN(recordId).toArrayLike(Buffer, "le", 4),
+    ],
+    programId
+  );
+  return { address, bump };
+}
+
+export function getUserVaultPda(
+  userPayment: PublicKey,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.USER_VAULT), userPayment.toBuffer()],
+    programId
+  );
+  return { address, bump };
+}
+
+export function getSolVaultPda(
+  userPayment: PublicKey,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.SOL_VAULT), userPayment.toBuffer()],
+    programId
+  );
+  return { address, bump };
+}
+
+export function getUsageReportPda(
+  paymentPolicy: PublicKey,
+  recordId: number,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(SEEDS.USAGE_REPORT),
+      paymentPolicy.toBuffer(),
+      new BN(recordId).toArrayLike(Buffer, "le", 4),
+    ],
+    programId
+  );
+  return { address, bump };
+}
+
+export function getRecipientSpendingCapPda(
+  userPayment: PublicKey,
+  recipient: PublicKey,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.RECIPIENT_CAP), userPayment.toBuffer(), recipient.toBuffer()],
+    programId
+  );
+  return { address, bump };
+}
+
+export function getDenylistEntryPda(
+  address: PublicKey,
+  programId: PublicKey
+): PdaResult {
+  const [entry, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.DENYLIST), address.toBuffer()],
+    programId
+  );
+  return { address: entry, bump };
+}
+
+export function getRecipientSettingsPda(
+  recipient: PublicKey,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.RECIPIENT_SETTINGS), recipient.toBuffer()],
+    programId
+  );
+  return { address, bump };
+}
+
+export function getMerchantPda(
+  recipient: PublicKey,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.MERCHANT), recipient.toBuffer()],
+    programId
+  );
+  return { address, bump };
+}
+
+/** `kind` identifies the changed config field, see `getConfigChangeKind` */
+export function getPendingConfigChangePda(
+  kind: number,
+  programId: PublicKey
+): PdaResult {
+  const [address, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.PENDING_CONFIG_CHANGE), Buffer.from([kind])],
+    programId
+  );
+  return { address, bump };
+}
@@ -10,6 +10,7 @@ import {
   createApproveInstruction,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
+  NATIVE_MINT,
 } from "@solana/spl-token";
 import * as anchor from "@coral-xyz/anchor";
 import BN from "bn.js";
@@ -19,6 +20,18 @@ import {
   getUserPaymentPda,
   getPaymentPolicyPda,
   getPaymentsDelegatePda,
+  getGatewayBondPda,
+  getGatewaySignersPda,
+  getEscrowPda,
+  getEscrowVaultPda,
+  getUserVaultPda,
+  getSolVaultPda,
+  getUsageReportPda,
+  getRecipientSpendingCapPda,
+  getDenylistEntryPda,
+  getRecipientSettingsPda,
+  getMerchantPda,
+  getPendingConfigChangePda,
 } from "./pda";
 import type {
   PolicyType,
@@ -27,7 +40,15 @@ import type {
   PaymentPolicy,
   PaymentGateway,
   ProgramConfig,
+  GatewaySigners,
+  GatewaySignerRole,
+  PaymentEscrow,
+  Merchant,
+  ConfigRole,
+  ConfigChange,
+  PendingConfigChange,
 } from "./types.js";
+import { encodeMemo } from "./utils";
 import IDL from "../../target/idl/recurring_payments.json"; // with { type: "json" };
 import { RecurringPayments } from "../../target/types/recurring_payments.js";
 
@@ -83,6 +104,7 @@ export class Tributary {
       config: configPda,
       tokenAccount: getAssociatedTokenAddressSync(tokenMint, owner),
       tokenMint: tokenMint,
+      payerDenylistEntry: this.getDenylistEntryPda(owner).address,
       userPayment: userPaymentPda,
       systemProgram: SystemProgram.programId,
     };
@@ -100,7 +122,7 @@ export class Tributary {
     name: string,
     url: string
   ): Promise<TransactionInstruction> {
-    const admin = this.provider.publicKey;
+    const gatewayApprover = this.provider.publicKey;
     const gateway = this.getGatewayPda(authority).address;
     const { address: configPda } = getConfigPda(this.programId);
 
@@ -118,7 +140,7 @@ export class Tributary {
     }
 
     const accounts = {
-      admin: admin,
+      gatewayApprover: gatewayApprover,
       authority: authority,
       gateway: gateway,
       config: configPda,
@@ -131,6 +153,135 @@ export class Tributary {
       .instruction();
   }
 
+  /**
+   * Assign a config role, as the admin. Assigning the default public key
+   * hands the role back to the admin.
+   */
+  async setConfigRole(
+    role: ConfigRole,
+    holder: PublicKey
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .setConfigRole(role, holder)
+      .accountsStrict({
+        admin: this.provider.publicKey,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Identifies the config field a change touches, one change per field is pending */
+  getConfigChangeKind(change: ConfigChange): number {
+    if ("protocolFeeBps" in change) return 0;
+    if ("feeRecipient" in change) return 1;
+    if ("maxPoliciesPerUser" in change) return 2;
+    return 3;
+  }
+
+  /**
+   * Queue a config change as the role it requires. It can be applied once
+   * the timelock has passed.
+   */
+  async queueConfigChange(
+    change: ConfigChange
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .queueConfigChange(change)
+      .accountsStrict({
+        authority: this.provider.publicKey,
+        config: configPda,
+        pendingConfigChange: this.getPendingConfigChangePda(
+          this.getConfigChangeKind(change)
+        ).address,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  /**
+   * Apply a queued config change whose timelock has passed and that has not
+   * lapsed. Anyone can call this.
+   */
+  async executeConfigChange(kind: number): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const { address: pendingPda } = this.getPendingConfigChangePda(kind);
+    const pending = await this.program.account.pendingConfigChange.fetch(
+      pendingPda
+    );
+
+    return await this.program.methods
+      .executeConfigChange()
+      .accountsStrict({
+        executor: this.provider.publicKey,
+        config: configPda,
+        pendingConfigChange: pendingPda,
+        queuedBy: pending.queuedBy,
+      })
+      .instruction();
+  }
+
+  /**
+   * Cancel a queued config change. The role that queued it can cancel it until
+   * it is executed, anyone can cancel it once it has lapsed.
+   */
+  async cancelConfigChange(kind: number): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const { address: pendingPda } = this.getPendingConfigChangePda(kind);
+    const pending = await this.program.account.pendingConfigChange.fetch(
+      pendingPda
+    );
+
+    return await this.program.methods
+      .cancelConfigChange()
+      .accountsStrict({
+        authority: this.provider.publicKey,
+        config: configPda,
+        pendingConfigChange: pendingPda,
+        queuedBy: pending.queuedBy,
+      })
+      .instruction();
+  }
+
+  async getPendingConfigChange(
+    kind: number
+  ): Promise<PendingConfigChange | null> {
+    return await this.program.account.pendingConfigChange.fetchNullable(
+      this.getPendingConfigChangePda(kind).address
+    );
+  }
+
+  /** Pause the program as the pauser, or lift a pause as the admin */
+  async setEmergencyPause(paused: boolean): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .setEmergencyPause(paused)
+      .accountsStrict({
+        authority: this.provider.publicKey,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Set the bounty for closing finished policies, as the fee manager */
+  async setClosePolicyBounty(
+    bountyLamports: BN
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .setClosePolicyBounty(bountyLamports)
+      .accountsStrict({
+        feeManager: this.provider.publicKey,
+        config: configPda,
+      })
+      .instruction();
+  }
+
   async createPaymentPolicy(
     tokenMint: PublicKey,
     recipient: PublicKey,
@@ -140,18 +291,10 @@ export class Tributary {
     maxRenewals: number | null,
     paymentFrequency: PaymentFrequency,
     memo: number[],
-    startTime?: BN | null
+    startTime?: BN | null,
+    holdSeconds: number = 0,
+    endsAt: BN = new BN(0)
   ): Promise<TransactionInstruction> {
-    const user = this.provider.publicKey;
-    const { address: configPda } = getConfigPda(this.programId);
-    const { address: userPaymentPda } = this.getUserPaymentPda(user, tokenMint);
-    const userPayment: UserPayment | null =
-      await this.program.account.userPayment.fetchNullable(userPaymentPda);
-    let policyId: number = 1;
-    if (userPayment) {
-      policyId = userPayment.activePoliciesCount + 1;
-    }
-    const paymentPolicy = this.getPaymentPolicyPda(userPaymentPda, policyId);
     const nextPaymentDue = startTime || new BN(Math.floor(Date.now() / 1000));
     const policyType: PolicyType = {
       subscription: {
@@ -160,13 +303,188 @@ export class Tributary {
         maxRenewals: maxRenewals,
         paymentFrequency: paymentFrequency,
         nextPaymentDue: nextPaymentDue,
-        padding: new Array(97).fill(0),
+        endsAt: endsAt,
+        padding: new Array(89).fill(0),
+      },
+    };
+    return await this.createPolicyInstruction(
+      tokenMint,
+      recipient,
+      gateway,
+      policyType,
+      memo,
+      holdSeconds
+    );
+  }
+
+  /**
+   * Create a continuous payment that vests `ratePerSecond` from `start` until
+   * `end`. The recipient or gateway claims what has vested with `executePayment`.
+   */
+  async createStreamPolicy(
+    tokenMint: PublicKey,
+    recipient: PublicKey,
+    gateway: PublicKey,
+    ratePerSecond: BN,
+    start: BN,
+    end: BN,
+    memo: number[]
+  ): Promise<TransactionInstruction> {
+    const policyType: PolicyType = {
+      stream: {
+        ratePerSecond,
+        start,
+        end,
+        withdrawn: new BN(0),
+        padding: new Array(96).fill(0),
+      },
+    };
+    return await this.createPolicyInstruction(
+      tokenMint,
+      recipient,
+      gateway,
+      policyType,
+      memo
+    );
+  }
+
+  /**
+   * Create a usage based policy. The gateway reports the units used in each
+   * period and is paid `unitPrice` per unit, at most `maxUnitsPerPeriod`.
+   */
+  async createMeteredPolicy(
+    tokenMint: PublicKey,
+    recipient: PublicKey,
+    gateway: PublicKey,
+    unitPrice: BN,
+    maxUnitsPerPeriod: BN,
+    period: PaymentFrequency,
+    memo: number[],
+    firstPeriodEnd?: BN | null
+  ): Promise<TransactionInstruction> {
+    const policyType: PolicyType = {
+      metered: {
+        unitPrice,
+        maxUnitsPerPeriod,
+        period,
+        nextPaymentDue:
+          firstPeriodEnd || new BN(Math.floor(Date.now() / 1000)),
+        padding: new Array(95).fill(0),
+      },
+    };
+    return await this.createPolicyInstruction(
+      tokenMint,
+      recipient,
+      gateway,
+      policyType,
+      memo
+    );
+  }
+
+  /**
+   * Create a direct debit mandate. Each period the gateway or the recipient
+   * pulls an amount of its choosing up to `maxAmount`, and optionally at most
+   * `rollingCap` across the last `rollingPeriods` periods.
+   */
+  async createVariablePolicy(
+    tokenMint: PublicKey,
+    recipient: PublicKey,
+    gateway: PublicKey,
+    maxAmount: BN,
+    period: PaymentFrequency,
+    memo: number[],
+    rollingPeriods: number = 0,
+    rollingCap: BN = new BN(0),
+    startTime?: BN | null
+  ): Promise<TransactionInstruction> {
+    const policyType: PolicyType = {
+      variable: {
+        maxAmount,
+        period,
+        nextPaymentDue: startTime || new BN(Math.floor(Date.now() / 1000)),
+        rollingPeriods,
+        rollingCap,
+        recentAmounts: new Array(8).fill(new BN(0)),
+        padding: new Array(30).fill(0),
+      },
+    };
+    return await this.createPolicyInstruction(
+      tokenMint,
+      recipient,
+      gateway,
+      policyType,
+      memo
+    );
+  }
+
+  /**
+   * Create a subscription priced in fiat. Each payment converts `fiatAmount`
+   * (with 6 decimals) into tokens at the price of the Pyth `priceFeed`, which
+   * must carry the price of `feedId` and may deviate at most `maxSlippageBps`
+   * from its moving average. The recipient has to accept the policy before it
+   * is charged.
+   */
+  async createFiatSubscriptionPolicy(
+    tokenMint: PublicKey,
+    recipient: PublicKey,
+    gateway: PublicKey,
+    fiatAmount: BN,
+    priceFeed: PublicKey,
+    feedId: number[],
+    paymentFrequency: PaymentFrequency,
+    maxSlippageBps: number,
+    memo: number[],
+    startTime?: BN | null
+  ): Promise<TransactionInstruction> {
+    const policyType: PolicyType = {
+      fiatSubscription: {
+        fiatAmount,
+        priceFeed,
+        paymentFrequency,
+        nextPaymentDue: startTime || new BN(Math.floor(Date.now() / 1000)),
+        maxSlippageBps,
+        feedId,
+        padding: new Array(37).fill(0),
       },
     };
+    return await this.createPolicyInstruction(
+      tokenMint,
+      recipient,
+      gateway,
+      policyType,
+      memo
+    );
+  }
+
+  /** Create a payment policy of any policy type for the connected wallet */
+  async createPolicyInstruction(
+    tokenMint: PublicKey,
+    recipient: PublicKey,
+    gateway: PublicKey,
+    policyType: PolicyType,
+    memo: number[],
+    holdSeconds: number = 0
+  ): Promise<TransactionInstruction> {
+    const user = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+    const { address: userPaymentPda } = this.getUserPaymentPda(user, tokenMint);
+    const userPayment: UserPayment | null =
+      await this.program.account.userPayment.fetchNullable(userPaymentPda);
+    const paymentPolicy = this.getPaymentPolicyPda(
+      userPaymentPda,
+      this.getNextPolicyId(userPayment)
+    );
+    const merchant = this.getMerchantPda(recipient).address;
+    const merchantInfo = await this.connection.getAccountInfo(merchant);
+
     const accounts = {
       user: user,
       userPayment: userPaymentPda,
       recipient: recipient,
+      recipientSettings: this.getRecipientSettingsPda(recipient).address,
+      payerDenylistEntry: this.getDenylistEntryPda(user).address,
+      recipientDenylistEntry: this.getDenylistEntryPda(recipient).address,
+      merchant: merchantInfo ? merchant : null,
       tokenMint: tokenMint,
       gateway: gateway,
       config: configPda,
@@ -174,11 +492,21 @@ export class Tributary {
       systemProgram: SystemProgram.programId,
     };
     return await this.program.methods
-      .createPaymentPolicy(policyType, memo)
+      .createPaymentPolicy(policyType, memo, holdSeconds)
       .accountsStrict(accounts)
       .instruction();
   }
 
+  /** The id the next policy of a user payment is created with */
+  getNextPolicyId(userPayment: UserPayment | null): number {
+    if (!userPayment) {
+      return 1;
+    }
+    // Ids are never reused, accounts from before ids were tracked continue
+    // from their policy count
+    return Math.max(userPayment.lastPolicyId, userPayment.activePoliciesCount) + 1;
+  }
+
   async createSubscriptionInstruction(
     tokenMint: PublicKey,
     recipient: PublicKey,
@@ -190,7 +518,9 @@ export class Tributary {
     memo: number[],
     startTime?: BN | null,
     approvalAmount?: BN,
-    executeImmediately?: boolean
+    executeImmediately?: boolean,
+    holdSeconds: number = 0,
+    endsAt: BN = new BN(0)
   ): Promise<TransactionInstruction[]> {
     const user = this.provider.publicKey;
     const { address: userPaymentPda } = this.getUserPaymentPda(user, tokenMint);
@@ -222,43 +552,21 @@ export class Tributary {
       instructions.push(createUserPaymentIx);
     }
 
-    // Determine policy ID
-    let policyId: number = 1;
-    if (userPayment) {
-      policyId = userPayment.activePoliciesCount + 1;
-    }
-
-    // Build policy type
-    const nextPaymentDue = startTime || new BN(Math.floor(Date.now() / 1000));
-    const policyType: PolicyType = {
-      subscription: {
-        amount: amount,
-        autoRenew: autoRenew,
-        maxRenewals: maxRenewals,
-        paymentFrequency: paymentFrequency,
-        nextPaymentDue: nextPaymentDue,
-        padding: new Array(97).fill(0),
-      },
-    };
-
-    // Create payment policy instruction
+    const policyId = this.getNextPolicyId(userPayment);
     const paymentPolicyPda = this.getPaymentPolicyPda(userPaymentPda, policyId);
-    const { address: configPda } = getConfigPda(this.programId);
-    const accounts = {
-      user: user,
-      config: configPda,
-      userPayment: userPaymentPda,
-      recipient: recipient,
-      tokenMint: tokenMint,
-      gateway: gateway,
-      paymentPolicy: paymentPolicyPda.address,
-      systemProgram: SystemProgram.programId,
-    };
-
-    const createPaymentPolicyIx = await this.program.methods
-      .createPaymentPolicy(policyType, memo)
-      .accountsStrict(accounts)
-      .instruction();
+    const createPaymentPolicyIx = await this.createPaymentPolicy(
+      tokenMint,
+      recipient,
+      gateway,
+      amount,
+      autoRenew,
+      maxRenewals,
+      paymentFrequency,
+      memo,
+      startTime,
+      holdSeconds,
+      endsAt
+    );
 
     instructions.push(createPaymentPolicyIx);
 
@@ -319,7 +627,9 @@ export class Tributary {
     recipient?: PublicKey,
     tokenMint?: PublicKey,
     gateway?: PublicKey,
-    user?: PublicKey
+    user?: PublicKey,
+    amount: BN | null = null,
+    invoiceReference: number[] | null = null
   ): Promise<TransactionInstruction[]> {
     const instructions: TransactionInstruction[] = [];
     const authority = this.provider.publicKey;
@@ -348,6 +658,12 @@ export class Tributary {
       }
     }
 
+    if (userPayment && this.isNativeSol(userPayment)) {
+      return [
+        await this.executeSolPayment(paymentPolicyPda, amount, invoiceReference),
+      ];
+    }
+
     _tokenMint = _tokenMint || tokenMint;
     _recipient = _recipient || recipient;
     _gateway = _gateway || gateway;
@@ -385,7 +701,9 @@ export class Tributary {
       _user,
       _tokenMint
     );
-    const tokenAccount = getAssociatedTokenAddressSync(_tokenMint, _user);
+    const tokenAccount = userPayment
+      ? this.getFundingAccount(userPayment)
+      : getAssociatedTokenAddressSync(_tokenMint, _user);
 
     // Payment Recipient ATA
     const recipientTokenAccount = getAssociatedTokenAddressSync(
@@ -447,22 +765,82 @@ export class Tributary {
       instructions.push(createAtaIx);
     }
 
+    // Keys other than the gateway's primary signer are authorized by its signer set
+    const gatewaySigners = await this.getGatewaySigners(_gateway);
+
+    // Anyone else executes as a keeper and is paid a bounty into their ATA
+    const isPayeePull =
+      !!paymentPolicy &&
+      ("stream" in paymentPolicy.policyType ||
+        "variable" in paymentPolicy.policyType);
+    const isKeeper =
+      !gatewayAccount!.signer.equals(authority) &&
+      !_user.equals(authority) &&
+      !(isPayeePull && _recipient.equals(authority)) &&
+      !(gatewaySigners?.signers || []).some((entry) =>
+        entry.signer.equals(authority)
+      );
+    let keeperTokenAccount: PublicKey | null = null;
+    if (isKeeper) {
+      keeperTokenAccount = getAssociatedTokenAddressSync(_tokenMint, authority);
+      const keeperAccountInfo = await this.connection.getAccountInfo(
+        keeperTokenAccount
+      );
+      if (!keeperAccountInfo) {
+        instructions.push(
+          createAssociatedTokenAccountInstruction(
+            authority,
+            keeperTokenAccount,
+            authority,
+            _tokenMint,
+            TOKEN_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID
+          )
+        );
+      }
+    }
+
+    // While the policy has a hold period the recipient's share goes into escrow
+    const recordId = paymentPolicy ? paymentPolicy.paymentCount + 1 : 1;
+    const holdsPayment = !!paymentPolicy && paymentPolicy.holdSeconds > 0;
+
     const accounts = {
       feePayer: authority,
       paymentsDelegate: this.getPaymentsDelegatePda().address,
       paymentPolicy: paymentPolicyPda,
       userPayment: userPaymentPda,
       gateway: _gateway,
+      gatewaySigners: gatewaySigners
+        ? this.getGatewaySignersPda(_gateway).address
+        : null,
       config: configPda,
       userTokenAccount: tokenAccount,
       recipientTokenAccount,
       gatewayFeeAccount: gatewayFeeAccount,
       protocolFeeAccount: protocolFeeAccount,
+      recipientSpendingCap: this.getRecipientSpendingCapPda(
+        userPaymentPda,
+        _recipient
+      ).address,
+      payerDenylistEntry: this.getDenylistEntryPda(_user).address,
+      recipientDenylistEntry: this.getDenylistEntryPda(_recipient).address,
+      keeperTokenAccount,
+      ...(paymentPolicy
+        ? this.getPolicyTypeAccounts(paymentPolicyPda, paymentPolicy)
+        : { usageReport: null, priceFeed: null }),
+      escrow: holdsPayment
+        ? this.getEscrowPda(paymentPolicyPda, recordId).address
+        : null,
+      escrowVault: holdsPayment
+        ? this.getEscrowVaultPda(paymentPolicyPda, recordId).address
+        : null,
+      tokenMint: _tokenMint,
       tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
     };
     instructions.push(
       await this.program.methods
-        .executePayment()
+        .executePayment(amount, invoiceReference)
         .accountsStrict(accounts)
         .instruction()
     );
@@ -470,75 +848,1428 @@ export class Tributary {
     return instructions;
   }
 
-  // Helper methods to get PDAs
-  getConfigPda() {
-    return getConfigPda(this.programId);
-  }
-
-  getGatewayPda(gatewayAuthority: PublicKey) {
-    return getGatewayPda(gatewayAuthority, this.programId);
-  }
-
-  getUserPaymentPda(user: PublicKey, tokenMint: PublicKey) {
-    return getUserPaymentPda(user, tokenMint, this.programId);
-  }
-
-  getPaymentPolicyPda(userPayment: PublicKey, policyId: number) {
-    return getPaymentPolicyPda(userPayment, policyId, this.programId);
-  }
-
-  /**
-   * Helper method to get the Payments Delegate PDA.
-   * @returns The PdaResult for the Payments Delegate PDA.
-   */
-  getPaymentsDelegatePda() {
-    return getPaymentsDelegatePda(this.programId);
-  }
-
-  async changePaymentPolicyStatus(
-    tokenMint: PublicKey,
-    policyId: number,
-    newStatus: { active: {} } | { paused: {} }
+  async setGatewayBondConfig(
+    bondMint: PublicKey,
+    bondAmount: BN,
+    cooldownSeconds: BN
   ): Promise<TransactionInstruction> {
-    const owner = this.provider.publicKey;
-    const { address: userPaymentPda } = this.getUserPaymentPda(
-      owner,
-      tokenMint
-    );
-    const { address: paymentPolicyPda } = this.getPaymentPolicyPda(
-      userPaymentPda,
-      policyId
-    );
     const { address: configPda } = getConfigPda(this.programId);
 
-    const accounts = {
-      owner: owner,
-      config: configPda,
-      userPayment: userPaymentPda,
-      tokenMint: tokenMint,
-      paymentPolicy: paymentPolicyPda,
-    };
-
     return await this.program.methods
-      .changePaymentPolicyStatus(policyId, newStatus)
-      .accountsStrict(accounts)
+      .setGatewayBondConfig(bondAmount, cooldownSeconds)
+      .accountsStrict({
+        feeManager: this.provider.publicKey,
+        config: configPda,
+        bondMint,
+      })
       .instruction();
   }
 
-  async deletePaymentPolicy(
-    tokenMint: PublicKey,
-    policyId: number
+  async registerGateway(
+    gatewayFeeBps: number,
+    gatewayFeeRecipient: PublicKey,
+    name: string,
+    url: string
   ): Promise<TransactionInstruction> {
-    const owner = this.provider.publicKey;
-    const { address: userPaymentPda } = this.getUserPaymentPda(
-      owner,
-      tokenMint
-    );
-    const { address: paymentPolicyPda } = this.getPaymentPolicyPda(
+    const authority = this.provider.publicKey;
+    const { address: gatewayPda } = this.getGatewayPda(authority);
+    const { address: configPda } = getConfigPda(this.programId);
+    const config = await this.program.account.programConfig.fetch(configPda);
+
+    const nameBytes = new Array(32).fill(0);
+    const nameBuffer = Buffer.from(name, "utf8");
+    for (let i = 0; i < Math.min(nameBuffer.length, 32); i++) {
+      nameBytes[i] = nameBuffer[i];
+    }
+
+    const urlBytes = new Array(64).fill(0);
+    const urlBuffer = Buffer.from(url, "utf8");
+    for (let i = 0; i < Math.min(urlBuffer.length, 64); i++) {
+      urlBytes[i] = urlBuffer[i];
+    }
+
+    const accounts = {
+      authority,
+      gateway: gatewayPda,
+      config: configPda,
+      feeRecipient: gatewayFeeRecipient,
+      bondMint: config.gatewayBondMint,
+      bondVault: this.getGatewayBondPda(gatewayPda).address,
+      authorityTokenAccount: getAssociatedTokenAddressSync(
+        config.gatewayBondMint,
+        authority
+      ),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+
+    return await this.program.methods
+      .registerGateway(gatewayFeeBps, nameBytes, urlBytes)
+      .accountsStrict(accounts)
+      .instruction();
+  }
+
+  async approveGateway(
+    gatewayAuthority: PublicKey
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .approveGateway()
+      .accountsStrict({
+        gatewayApprover: this.provider.publicKey,
+        gateway: this.getGatewayPda(gatewayAuthority).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  async slashGatewayBond(
+    gatewayAuthority: PublicKey,
+    amount: BN
+  ): Promise<TransactionInstruction> {
+    const { address: gatewayPda } = this.getGatewayPda(gatewayAuthority);
+    const { address: configPda } = getConfigPda(this.programId);
+    const config = await this.program.account.programConfig.fetch(configPda);
+    const gateway = await this.program.account.paymentGateway.fetch(
+      gatewayPda
+    );
+
+    return await this.program.methods
+      .slashGatewayBond(amount)
+      .accountsStrict({
+        gatewayApprover: this.provider.publicKey,
+        gateway: gatewayPda,
+        bondVault: this.getGatewayBondPda(gatewayPda).address,
+        config: configPda,
+        protocolFeeAccount: getAssociatedTokenAddressSync(
+          gateway.bondMint,
+          config.feeRecipient
+        ),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  async topUpGatewayBond(amount: BN): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: gatewayPda } = this.getGatewayPda(authority);
+    const gateway = await this.program.account.paymentGateway.fetch(
+      gatewayPda
+    );
+
+    return await this.program.methods
+      .topUpGatewayBond(amount)
+      .accountsStrict({
+        authority,
+        gateway: gatewayPda,
+        bondVault: this.getGatewayBondPda(gatewayPda).address,
+        authorityTokenAccount: getAssociatedTokenAddressSync(
+          gateway.bondMint,
+          authority
+        ),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  async deregisterGateway(): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .deregisterGateway()
+      .accountsStrict({
+        authority,
+        gateway: this.getGatewayPda(authority).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  async withdrawGatewayBond(): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: gatewayPda } = this.getGatewayPda(authority);
+    const gateway = await this.program.account.paymentGateway.fetch(
+      gatewayPda
+    );
+
+    return await this.program.methods
+      .withdrawGatewayBond()
+      .accountsStrict({
+        authority,
+        gateway: gatewayPda,
+        gatewaySigners: this.getGatewaySignersPda(gatewayPda).address,
+        bondVault: this.getGatewayBondPda(gatewayPda).address,
+        authorityTokenAccount: getAssociatedTokenAddressSync(
+          gateway.bondMint,
+          authority
+        ),
+        config: getConfigPda(this.programId).address,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  /**
+   * Let anyone execute payments of this gateway once they are
+   * `keeperGraceSeconds` overdue, for a share of the gateway fee.
+   */
+  async setGatewayKeeperSettings(
+    keeperGraceSeconds: BN,
+    keeperBountyBps: number
+  ): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .setGatewayKeeperSettings(keeperGraceSeconds, keeperBountyBps)
+      .accountsStrict({
+        authority,
+        gateway: this.getGatewayPda(authority).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  async createGatewaySigners(): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: gatewayPda } = this.getGatewayPda(authority);
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .createGatewaySigners()
+      .accountsStrict({
+        authority,
+        gateway: gatewayPda,
+        gatewaySigners: this.getGatewaySignersPda(gatewayPda).address,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  /**
+   * Add a key to the gateway's signer set, or update its role and expiry.
+   * `expiresAt` is a unix timestamp, zero for no expiry.
+   */
+  async addGatewaySigner(
+    signer: PublicKey,
+    role: GatewaySignerRole,
+    expiresAt: BN
+  ): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: gatewayPda } = this.getGatewayPda(authority);
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .addGatewaySigner(signer, role, expiresAt)
+      .accountsStrict({
+        authority,
+        gateway: gatewayPda,
+        gatewaySigners: this.getGatewaySignersPda(gatewayPda).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  async removeGatewaySigner(
+    signer: PublicKey
+  ): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: gatewayPda } = this.getGatewayPda(authority);
+
+    return await this.program.methods
+      .removeGatewaySigner(signer)
+      .accountsStrict({
+        authority,
+        gateway: gatewayPda,
+        gatewaySigners: this.getGatewaySignersPda(gatewayPda).address,
+      })
+      .instruction();
+  }
+
+  /**
+   * Refund part of a payment to the payer. The signer is either the policy's
+   * recipient or an operator key of its gateway, and pays from its own ATA,
+   * or in lamports for native SOL policies. Each of the latest payments can be
+   * refunded up to the amount it charged.
+   */
+  async refundPayment(
+    paymentPolicyPda: PublicKey,
+    recordId: number,
+    amount: BN
+  ): Promise<TransactionInstruction> {
+    const refunder = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+    const userPayment = await this.program.account.userPayment.fetch(
+      paymentPolicy.userPayment
+    );
+
+    const isRecipient = paymentPolicy.recipient.equals(refunder);
+    const gatewaySigners = isRecipient
+      ? null
+      : await this.getGatewaySigners(paymentPolicy.gateway);
+
+    if (this.isNativeSol(userPayment)) {
+      return await this.program.methods
+        .refundSolPayment(recordId, amount)
+        .accountsStrict({
+          refunder,
+          paymentPolicy: paymentPolicyPda,
+          gateway: isRecipient ? null : paymentPolicy.gateway,
+          gatewaySigners: gatewaySigners
+            ? this.getGatewaySignersPda(paymentPolicy.gateway).address
+            : null,
+          userPayment: paymentPolicy.userPayment,
+          owner: userPayment.owner,
+          config: configPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+    }
+
+    const accounts = {
+      refunder,
+      paymentPolicy: paymentPolicyPda,
+      gateway: isRecipient ? null : paymentPolicy.gateway,
+      gatewaySigners: gatewaySigners
+        ? this.getGatewaySignersPda(paymentPolicy.gateway).address
+        : null,
+      userPayment: paymentPolicy.userPayment,
+      refunderTokenAccount: getAssociatedTokenAddressSync(
+        userPayment.tokenMint,
+        refunder
+      ),
+      userTokenAccount: userPayment.tokenAccount,
+      config: configPda,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    return await this.program.methods
+      .refundPayment(recordId, amount)
+      .accountsStrict(accounts)
+      .instruction();
+  }
+
+  /**
+   * Raise the hold period of a policy. Only the recipient can call this, and
+   * the hold period the payer agreed to at creation can never be shortened.
+   */
+  async setPolicyHoldPeriod(
+    paymentPolicyPda: PublicKey,
+    holdSeconds: number
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+
+    return await this.program.methods
+      .setPolicyHoldPeriod(holdSeconds)
+      .accountsStrict({
+        recipient: this.provider.publicKey,
+        paymentPolicy: paymentPolicyPda,
+        userPayment: paymentPolicy.userPayment,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Dispute an escrowed payment before its hold period ends */
+  async openDispute(
+    paymentPolicyPda: PublicKey,
+    recordId: number
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+
+    return await this.program.methods
+      .openDispute()
+      .accountsStrict({
+        owner: this.provider.publicKey,
+        userPayment: paymentPolicy.userPayment,
+        paymentPolicy: paymentPolicyPda,
+        escrow: this.getEscrowPda(paymentPolicyPda, recordId).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Release an undisputed escrow to the recipient once its hold period ended */
+  async releaseEscrow(
+    paymentPolicyPda: PublicKey,
+    recordId: number
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const { address: escrowPda } = this.getEscrowPda(
+      paymentPolicyPda,
+      recordId
+    );
+    const escrow: PaymentEscrow = await this.program.account.paymentEscrow.fetch(
+      escrowPda
+    );
+
+    return await this.program.methods
+      .releaseEscrow()
+      .accountsStrict({
+        caller: this.provider.publicKey,
+        escrow: escrowPda,
+        paymentPolicy: paymentPolicyPda,
+        escrowVault: this.getEscrowVaultPda(paymentPolicyPda, recordId).address,
+        recipientTokenAccount: getAssociatedTokenAddressSync(
+          escrow.tokenMint,
+          escrow.recipient
+        ),
+        rentPayer: escrow.rentPayer,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  /**
+   * Settle a disputed escrow as a gateway operator, either to the recipient
+   * or back to the payer.
+   */
+  async resolveDispute(
+    paymentPolicyPda: PublicKey,
+    recordId: number,
+    releaseToRecipient: boolean
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+    const userPayment = await this.program.account.userPayment.fetch(
+      paymentPolicy.userPayment
+    );
+    const { address: escrowPda } = this.getEscrowPda(
+      paymentPolicyPda,
+      recordId
+    );
+    const escrow: PaymentEscrow = await this.program.account.paymentEscrow.fetch(
+      escrowPda
+    );
+    const gatewaySigners = await this.getGatewaySigners(paymentPolicy.gateway);
+
+    return await this.program.methods
+      .resolveDispute(releaseToRecipient)
+      .accountsStrict({
+        arbiter: this.provider.publicKey,
+        paymentPolicy: paymentPolicyPda,
+        userPayment: paymentPolicy.userPayment,
+        gateway: paymentPolicy.gateway,
+        gatewaySigners: gatewaySigners
+          ? this.getGatewaySignersPda(paymentPolicy.gateway).address
+          : null,
+        escrow: escrowPda,
+        escrowVault: this.getEscrowVaultPda(paymentPolicyPda, recordId).address,
+        destinationTokenAccount: releaseToRecipient
+          ? getAssociatedTokenAddressSync(escrow.tokenMint, escrow.recipient)
+          : userPayment.tokenAccount,
+        rentPayer: escrow.rentPayer,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  /**
+   * Prefund payments from a vault owned by the user payment account instead
+   * of token delegation.
+   */
+  async createUserPaymentVault(
+    tokenMint: PublicKey
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .createUserPaymentVault()
+      .accountsStrict({
+        owner,
+        userPayment: userPaymentPda,
+        tokenMint,
+        vault: this.getUserVaultPda(userPaymentPda).address,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  async depositToVault(
+    tokenMint: PublicKey,
+    amount: BN
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .depositToVault(amount)
+      .accountsStrict({
+        owner,
+        userPayment: userPaymentPda,
+        vault: this.getUserVaultPda(userPaymentPda).address,
+        ownerTokenAccount: getAssociatedTokenAddressSync(tokenMint, owner),
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  async withdrawFromVault(
+    tokenMint: PublicKey,
+    amount: BN
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+
+    return await this.program.methods
+      .withdrawFromVault(amount)
+      .accountsStrict({
+        owner,
+        userPayment: userPaymentPda,
+        vault: this.getUserVaultPda(userPaymentPda).address,
+        ownerTokenAccount: getAssociatedTokenAddressSync(tokenMint, owner),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  /**
+   * Close the prepaid vault, returning its balance to the payer's token account.
+   * Payments are pulled through the payments delegate again afterwards.
+   */
+  async closeUserPaymentVault(
+    tokenMint: PublicKey
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+    const userPayment = await this.program.account.userPayment.fetch(
+      userPaymentPda
+    );
+
+    return await this.program.methods
+      .closeUserPaymentVault()
+      .accountsStrict({
+        owner,
+        userPayment: userPaymentPda,
+        vault: userPayment.vault,
+        ownerTokenAccount: userPayment.tokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  /**
+   * Create a user payment that pays in native SOL from a lamport vault, so
+   * no wrapped SOL account is needed.
+   */
+  async createSolUserPayment(): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      NATIVE_MINT
+    );
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .createSolUserPayment()
+      .accountsStrict({
+        owner,
+        userPayment: userPaymentPda,
+        solVault: this.getSolVaultPda(userPaymentPda).address,
+        payerDenylistEntry: this.getDenylistEntryPda(owner).address,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  async depositSol(lamports: BN): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      NATIVE_MINT
+    );
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .depositSol(lamports)
+      .accountsStrict({
+        owner,
+        userPayment: userPaymentPda,
+        solVault: this.getSolVaultPda(userPaymentPda).address,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  async withdrawSol(lamports: BN): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      NATIVE_MINT
+    );
+
+    return await this.program.methods
+      .withdrawSol(lamports)
+      .accountsStrict({
+        owner,
+        userPayment: userPaymentPda,
+        solVault: this.getSolVaultPda(userPaymentPda).address,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  /**
+   * Execute a payment of a native SOL policy. The recipient and fee accounts
+   * are paid in lamports; fees too small to fund an empty fee account are
+   * skipped.
+   */
+  async executeSolPayment(
+    paymentPolicyPda: PublicKey,
+    amount: BN | null = null,
+    invoiceReference: number[] | null = null
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const config = await this.program.account.programConfig.fetch(configPda);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+    const userPayment = await this.program.account.userPayment.fetch(
+      paymentPolicy.userPayment
+    );
+    const gateway = await this.program.account.paymentGateway.fetch(
+      paymentPolicy.gateway
+    );
+    const gatewaySigners = await this.getGatewaySigners(paymentPolicy.gateway);
+
+    return await this.program.methods
+      .executeSolPayment(amount, invoiceReference)
+      .accountsStrict({
+        feePayer: this.provider.publicKey,
+        paymentPolicy: paymentPolicyPda,
+        userPayment: paymentPolicy.userPayment,
+        solVault: userPayment.vault,
+        gateway: paymentPolicy.gateway,
+        gatewaySigners: gatewaySigners
+          ? this.getGatewaySignersPda(paymentPolicy.gateway).address
+          : null,
+        config: configPda,
+        recipient: paymentPolicy.recipient,
+        recipientSpendingCap: this.getRecipientSpendingCapPda(
+          paymentPolicy.userPayment,
+          paymentPolicy.recipient
+        ).address,
+        payerDenylistEntry: this.getDenylistEntryPda(userPayment.owner).address,
+        recipientDenylistEntry: this.getDenylistEntryPda(
+          paymentPolicy.recipient
+        ).address,
+        gatewayFeeRecipient: gateway.feeRecipient,
+        protocolFeeRecipient: config.feeRecipient,
+        ...this.getPolicyTypeAccounts(paymentPolicyPda, paymentPolicy),
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  /**
+   * Accounts some policy types need to execute a payment: the usage report of
+   * a metered period and the price feed of a fiat priced subscription.
+   */
+  getPolicyTypeAccounts(
+    paymentPolicyPda: PublicKey,
+    paymentPolicy: PaymentPolicy
+  ): { usageReport: PublicKey | null; priceFeed: PublicKey | null } {
+    const policyType = paymentPolicy.policyType;
+    return {
+      usageReport:
+        "metered" in policyType
+          ? this.getUsageReportPda(
+              paymentPolicyPda,
+              paymentPolicy.paymentCount + 1
+            ).address
+          : null,
+      priceFeed:
+        "fiatSubscription" in policyType
+          ? policyType.fiatSubscription!.priceFeed
+          : null,
+    };
+  }
+
+  /** Report the units a metered policy used in its current period */
+  async submitUsageReport(
+    paymentPolicyPda: PublicKey,
+    units: BN
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+    const gatewaySigners = await this.getGatewaySigners(paymentPolicy.gateway);
+
+    return await this.program.methods
+      .submitUsageReport(units)
+      .accountsStrict({
+        reporter: this.provider.publicKey,
+        paymentPolicy: paymentPolicyPda,
+        gateway: paymentPolicy.gateway,
+        gatewaySigners: gatewaySigners
+          ? this.getGatewaySignersPda(paymentPolicy.gateway).address
+          : null,
+        usageReport: this.getUsageReportPda(
+          paymentPolicyPda,
+          paymentPolicy.paymentCount + 1
+        ).address,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  /**
+   * Cap what all policies of the connected wallet's user payment may charge
+   * within any `periodSeconds`. A limit of zero removes the cap.
+   */
+  async setSpendingLimit(
+    tokenMint: PublicKey,
+    limit: BN,
+    periodSeconds: BN
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .setSpendingLimit(limit, periodSeconds)
+      .accountsStrict({
+        owner,
+        userPayment: this.getUserPaymentPda(owner, tokenMint).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Cap what a single recipient may be paid within any `periodSeconds` */
+  async createRecipientSpendingCap(
+    tokenMint: PublicKey,
+    recipient: PublicKey,
+    limit: BN,
+    periodSeconds: BN
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+
+    return await this.program.methods
+      .createRecipientSpendingCap(recipient, limit, periodSeconds)
+      .accountsStrict({
+        owner,
+        userPayment: userPaymentPda,
+        recipientSpendingCap: this.getRecipientSpendingCapPda(
+          userPaymentPda,
+          recipient
+        ).address,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  async closeRecipientSpendingCap(
+    tokenMint: PublicKey,
+    recipient: PublicKey
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+
+    return await this.program.methods
+      .closeRecipientSpendingCap()
+      .accountsStrict({
+        owner,
+        userPayment: userPaymentPda,
+        recipientSpendingCap: this.getRecipientSpendingCapPda(
+          userPaymentPda,
+          recipient
+        ).address,
+      })
+      .instruction();
+  }
+
+  /**
+   * Draw future payments from another token account of the same mint. The
+   * payments delegate still has to be approved on the new account.
+   */
+  async updateUserPaymentTokenAccount(
+    tokenMint: PublicKey,
+    newTokenAccount: PublicKey
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .updateUserPaymentTokenAccount()
+      .accountsStrict({
+        owner,
+        userPayment: this.getUserPaymentPda(owner, tokenMint).address,
+        newTokenAccount,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Pause or resume all payments of the connected wallet for a token */
+  async setUserPaymentActive(
+    tokenMint: PublicKey,
+    isActive: boolean
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .setUserPaymentActive(isActive)
+      .accountsStrict({
+        owner,
+        userPayment: this.getUserPaymentPda(owner, tokenMint).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /**
+   * Close the user payment of the connected wallet once it has no policies
+   * left, reclaiming its rent and revoking the payments delegate.
+   */
+  async closeUserPayment(
+    tokenMint: PublicKey
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+    const userPayment = await this.program.account.userPayment.fetch(
+      userPaymentPda
+    );
+    const fundingMode = userPayment.fundingMode;
+
+    return await this.program.methods
+      .closeUserPayment()
+      .accountsStrict({
+        owner,
+        userPayment: userPaymentPda,
+        tokenAccount: "nativeSol" in fundingMode ? null : userPayment.tokenAccount,
+        vault: "tokenVault" in fundingMode ? userPayment.vault : null,
+        solVault: "nativeSol" in fundingMode ? userPayment.vault : null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  /** Stop a stream as its payer. What has vested so far stays claimable. */
+  async stopStream(paymentPolicyPda: PublicKey): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+
+    return await this.program.methods
+      .stopStream()
+      .accountsStrict({
+        owner: this.provider.publicKey,
+        userPayment: paymentPolicy.userPayment,
+        paymentPolicy: paymentPolicyPda,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /**
+   * Close a policy that will never charge again. Anyone can call this, the
+   * rent goes back to the payer minus the configured bounty for the caller.
+   */
+  async closeFinishedPolicy(
+    paymentPolicyPda: PublicKey
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+    const userPayment = await this.program.account.userPayment.fetch(
+      paymentPolicy.userPayment
+    );
+
+    return await this.program.methods
+      .closeFinishedPolicy()
+      .accountsStrict({
+        closer: this.provider.publicKey,
+        owner: userPayment.owner,
+        userPayment: paymentPolicy.userPayment,
+        paymentPolicy: paymentPolicyPda,
+        gateway: paymentPolicy.gateway,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /**
+   * Extend the end date or renewal cap of a subscription. The connected wallet
+   * signs as payer, the recipient has to sign the transaction as well. An
+   * `endsAt` of 0 lifts the end date.
+   */
+  async extendPolicyTerm(
+    paymentPolicyPda: PublicKey,
+    recipient: PublicKey,
+    endsAt: BN,
+    maxRenewals: number | null
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+
+    return await this.program.methods
+      .extendPolicyTerm(endsAt, maxRenewals)
+      .accountsStrict({
+        owner: this.provider.publicKey,
+        recipient,
+        userPayment: paymentPolicy.userPayment,
+        paymentPolicy: paymentPolicyPda,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Whether a user payment pays in native SOL from its lamport vault */
+  isNativeSol(userPayment: UserPayment): boolean {
+    return "nativeSol" in userPayment.fundingMode;
+  }
+
+  /** The account payments of a user payment are drawn from */
+  getFundingAccount(userPayment: UserPayment): PublicKey {
+    return "delegate" in userPayment.fundingMode
+      ? userPayment.tokenAccount
+      : userPayment.vault;
+  }
+
+  // Helper methods to get PDAs
+  getConfigPda() {
+    return getConfigPda(this.programId);
+  }
+
+  getGatewayPda(gatewayAuthority: PublicKey) {
+    return getGatewayPda(gatewayAuthority, this.programId);
+  }
+
+  getUserPaymentPda(user: PublicKey, tokenMint: PublicKey) {
+    return getUserPaymentPda(user, tokenMint, this.programId);
+  }
+
+  getPaymentPolicyPda(userPayment: PublicKey, policyId: number) {
+    return getPaymentPolicyPda(userPayment, policyId, this.programId);
+  }
+
+  getGatewayBondPda(gateway: PublicKey) {
+    return getGatewayBondPda(gateway, this.programId);
+  }
+
+  getGatewaySignersPda(gateway: PublicKey) {
+    return getGatewaySignersPda(gateway, this.programId);
+  }
+
+  getUserVaultPda(userPayment: PublicKey) {
+    return getUserVaultPda(userPayment, this.programId);
+  }
+
+  getSolVaultPda(userPayment: PublicKey) {
+    return getSolVaultPda(userPayment, this.programId);
+  }
+
+  getUsageReportPda(paymentPolicy: PublicKey, recordId: number) {
+    return getUsageReportPda(paymentPolicy, recordId, this.programId);
+  }
+
+  getRecipientSpendingCapPda(userPayment: PublicKey, recipient: PublicKey) {
+    return getRecipientSpendingCapPda(userPayment, recipient, this.programId);
+  }
+
+  getDenylistEntryPda(address: PublicKey) {
+    return getDenylistEntryPda(address, this.programId);
+  }
+
+  getRecipientSettingsPda(recipient: PublicKey) {
+    return getRecipientSettingsPda(recipient, this.programId);
+  }
+
+  getMerchantPda(recipient: PublicKey) {
+    return getMerchantPda(recipient, this.programId);
+  }
+
+  getPendingConfigChangePda(kind: number) {
+    return getPendingConfigChangePda(kind, this.programId);
+  }
+
+  getEscrowPda(paymentPolicy: PublicKey, recordId: number) {
+    return getEscrowPda(paymentPolicy, recordId, this.programId);
+  }
+
+  getEscrowVaultPda(paymentPolicy: PublicKey, recordId: number) {
+    return getEscrowVaultPda(paymentPolicy, recordId, this.programId);
+  }
+
+  /**
+   * Helper method to get the Payments Delegate PDA.
+   * @returns The PdaResult for the Payments Delegate PDA.
+   */
+  getPaymentsDelegatePda() {
+    return getPaymentsDelegatePda(this.programId);
+  }
+
+  async changePaymentPolicyStatus(
+    tokenMint: PublicKey,
+    policyId: number,
+    newStatus: { active: {} } | { paused: {} }
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+    const { address: paymentPolicyPda } = this.getPaymentPolicyPda(
+      userPaymentPda,
+      policyId
+    );
+    const { address: configPda } = getConfigPda(this.programId);
+
+    const accounts = {
+      owner: owner,
+      config: configPda,
+      userPayment: userPaymentPda,
+      tokenMint: tokenMint,
+      paymentPolicy: paymentPolicyPda,
+    };
+
+    return await this.program.methods
+      .changePaymentPolicyStatus(policyId, newStatus)
+      .accountsStrict(accounts)
+      .instruction();
+  }
+
+  /**
+   * Pause a policy of the connected wallet until `until`. The periods in
+   * between are skipped and the policy resumes on its own afterwards.
+   */
+  async pausePaymentPolicyUntil(
+    paymentPolicyPda: PublicKey,
+    until: BN
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+
+    return await this.program.methods
+      .pausePaymentPolicyUntil(until)
+      .accountsStrict({
+        owner: this.provider.publicKey,
+        userPayment: paymentPolicy.userPayment,
+        paymentPolicy: paymentPolicyPda,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /**
+   * Limit how long payers may pause a policy, as its recipient. 0 lifts the
+   * limit.
+   */
+  async setPolicyMaxPause(
+    paymentPolicyPda: PublicKey,
+    maxPauseSeconds: number
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .setPolicyMaxPause(maxPauseSeconds)
+      .accountsStrict({
+        recipient: this.provider.publicKey,
+        paymentPolicy: paymentPolicyPda,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /**
+   * Suspend, lift or cancel a policy as its recipient. Lifting a suspension
+   * hands the policy back to the payer as paused.
+   */
+  async recipientSetPolicyStatus(
+    paymentPolicyPda: PublicKey,
+    newStatus: { suspended: {} } | { paused: {} } | { cancelled: {} },
+    reasonCode: number = 0
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .recipientSetPolicyStatus(newStatus, reasonCode)
+      .accountsStrict({
+        recipient: this.provider.publicKey,
+        paymentPolicy: paymentPolicyPda,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /**
+   * Create the settings of the connected wallet as a recipient. With
+   * `requireAcceptance` new policies wait for the recipient to accept them.
+   */
+  async createRecipientSettings(
+    requireAcceptance: boolean
+  ): Promise<TransactionInstruction> {
+    const recipient = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .createRecipientSettings(requireAcceptance)
+      .accountsStrict({
+        recipient,
+        recipientSettings: this.getRecipientSettingsPda(recipient).address,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  async setRecipientRequireAcceptance(
+    requireAcceptance: boolean
+  ): Promise<TransactionInstruction> {
+    const recipient = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .setRecipientRequireAcceptance(requireAcceptance)
+      .accountsStrict({
+        recipient,
+        recipientSettings: this.getRecipientSettingsPda(recipient).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Accept a policy that is pending acceptance, as its recipient */
+  async acceptPolicy(
+    paymentPolicyPda: PublicKey
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .acceptPolicy()
+      .accountsStrict({
+        recipient: this.provider.publicKey,
+        paymentPolicy: paymentPolicyPda,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Accept several pending policies at once, as their recipient */
+  async acceptPolicies(
+    paymentPolicyPdas: PublicKey[]
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .acceptPolicies()
+      .accountsStrict({
+        recipient: this.provider.publicKey,
+        config: configPda,
+      })
+      .remainingAccounts(
+        paymentPolicyPdas.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .instruction();
+  }
+
+  /**
+   * Register the public profile of the connected wallet as a merchant.
+   * `logoHash` is the hash of the logo image served from `url`.
+   */
+  async registerMerchant(
+    name: string,
+    url: string,
+    logoHash: number[],
+    supportContact: string
+  ): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .registerMerchant(
+        encodeMemo(name, 32),
+        encodeMemo(url, 64),
+        logoHash,
+        encodeMemo(supportContact, 64)
+      )
+      .accountsStrict({
+        authority,
+        merchant: this.getMerchantPda(authority).address,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  /** Update the merchant profile. Changing it clears a gateway verification. */
+  async updateMerchant(
+    name: string,
+    url: string,
+    logoHash: number[],
+    supportContact: string
+  ): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .updateMerchant(
+        encodeMemo(name, 32),
+        encodeMemo(url, 64),
+        logoHash,
+        encodeMemo(supportContact, 64)
+      )
+      .accountsStrict({
+        authority,
+        merchant: this.getMerchantPda(authority).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Set the token account payments in `tokenMint` should settle to */
+  async setMerchantSettlementAccount(
+    tokenMint: PublicKey,
+    settlementTokenAccount: PublicKey
+  ): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .setMerchantSettlementAccount()
+      .accountsStrict({
+        authority,
+        merchant: this.getMerchantPda(authority).address,
+        tokenMint,
+        settlementTokenAccount,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  async removeMerchantSettlementAccount(
+    tokenMint: PublicKey
+  ): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .removeMerchantSettlementAccount(tokenMint)
+      .accountsStrict({
+        authority,
+        merchant: this.getMerchantPda(authority).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  /** Attest to a merchant profile as the gateway of the connected wallet */
+  async verifyMerchant(
+    merchantAuthority: PublicKey
+  ): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .verifyMerchant()
+      .accountsStrict({
+        authority,
+        gateway: this.getGatewayPda(authority).address,
+        merchant: this.getMerchantPda(merchantAuthority).address,
+        config: configPda,
+      })
+      .instruction();
+  }
+
+  async revokeMerchantVerification(
+    merchantAuthority: PublicKey
+  ): Promise<TransactionInstruction> {
+    const authority = this.provider.publicKey;
+
+    return await this.program.methods
+      .revokeMerchantVerification()
+      .accountsStrict({
+        authority,
+        gateway: this.getGatewayPda(authority).address,
+        merchant: this.getMerchantPda(merchantAuthority).address,
+      })
+      .instruction();
+  }
+
+  /** Denylist an address, as the Compliance role */
+  async addToDenylist(
+    address: PublicKey,
+    reasonCode: number = 0
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .addToDenylist(address, reasonCode)
+      .accountsStrict({
+        compliance: this.provider.publicKey,
+        config: configPda,
+        denylistEntry: this.getDenylistEntryPda(address).address,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  async removeFromDenylist(
+    address: PublicKey
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .removeFromDenylist()
+      .accountsStrict({
+        compliance: this.provider.publicKey,
+        config: configPda,
+        denylistEntry: this.getDenylistEntryPda(address).address,
+      })
+      .instruction();
+  }
+
+  /**
+   * Suspend policies paying or paid by a denylisted address, as the
+   * Compliance role. Policies it pays need its `userPayment`.
+   */
+  async suspendDenylistedPolicies(
+    address: PublicKey,
+    paymentPolicyPdas: PublicKey[],
+    userPayment: PublicKey | null = null
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    return await this.program.methods
+      .suspendDenylistedPolicies()
+      .accountsStrict({
+        compliance: this.provider.publicKey,
+        config: configPda,
+        denylistEntry: this.getDenylistEntryPda(address).address,
+        userPayment,
+      })
+      .remainingAccounts(
+        paymentPolicyPdas.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .instruction();
+  }
+
+  /**
+   * Lift the compliance suspension of policies, as the Compliance role, once
+   * their payers and recipients are off the denylist. Policies the recipient
+   * had suspended stay suspended, the others go back to their payers as
+   * paused.
+   */
+  async liftComplianceSuspension(
+    paymentPolicyPdas: PublicKey[]
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    const remainingAccounts = [];
+    for (const paymentPolicyPda of paymentPolicyPdas) {
+      const policy =
+        await this.program.account.paymentPolicy.fetch(paymentPolicyPda);
+      const userPayment = await this.program.account.userPayment.fetch(
+        policy.userPayment
+      );
+      remainingAccounts.push(
+        { pubkey: paymentPolicyPda, isSigner: false, isWritable: true },
+        { pubkey: policy.userPayment, isSigner: false, isWritable: false },
+        {
+          pubkey: this.getDenylistEntryPda(userPayment.owner).address,
+          isSigner: false,
+          isWritable: false,
+        },
+        {
+          pubkey: this.getDenylistEntryPda(policy.recipient).address,
+          isSigner: false,
+          isWritable: false,
+        }
+      );
+    }
+
+    return await this.program.methods
+      .liftComplianceSuspension()
+      .accountsStrict({
+        compliance: this.provider.publicKey,
+        config: configPda,
+      })
+      .remainingAccounts(remainingAccounts)
+      .instruction();
+  }
+
+  async deletePaymentPolicy(
+    tokenMint: PublicKey,
+    policyId: number
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+    const { address: paymentPolicyPda } = this.getPaymentPolicyPda(
       userPaymentPda,
       policyId
     );
     const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
 
     const accounts = {
       owner: owner,
@@ -546,6 +2277,7 @@ export class Tributary {
       userPayment: userPaymentPda,
       tokenMint: tokenMint,
       paymentPolicy: paymentPolicyPda,
+      gateway: paymentPolicy.gateway,
     };
 
     return await this.program.methods
@@ -554,23 +2286,168 @@ export class Tributary {
       .instruction();
   }
 
+  async acceptPolicyFees(
+    tokenMint: PublicKey,
+    policyId: number
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+    const { address: paymentPolicyPda } = this.getPaymentPolicyPda(
+      userPaymentPda,
+      policyId
+    );
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+
+    const accounts = {
+      owner: owner,
+      userPayment: userPaymentPda,
+      tokenMint: tokenMint,
+      paymentPolicy: paymentPolicyPda,
+      gateway: paymentPolicy.gateway,
+      config: configPda,
+    };
+
+    return await this.program.methods
+      .acceptPolicyFees(policyId)
+      .accountsStrict(accounts)
+      .instruction();
+  }
+
   async deletePaymentGateway(
-    gatewayAuthority: PublicKey
+    gatewayAuthority: PublicKey,
+    successorAuthority?: PublicKey
   ): Promise<TransactionInstruction> {
-    const admin = this.provider.publicKey;
+    const gatewayApprover = this.provider.publicKey;
     const { address: gatewayPda } = this.getGatewayPda(gatewayAuthority);
     const { address: configPda } = getConfigPda(this.programId);
 
+    // Remaining policies are handed to the successor, which has to co-sign
+    let remainingAccounts: anchor.web3.AccountMeta[] = [];
+    if (successorAuthority) {
+      const policies = await this.getPaymentPoliciesByGateway(gatewayPda);
+      remainingAccounts = policies.map((policy) => ({
+        pubkey: policy.publicKey,
+        isSigner: false,
+        isWritable: true,
+      }));
+    }
+
     const accounts = {
-      admin: admin,
+      gatewayApprover: gatewayApprover,
       authority: gatewayAuthority,
       gateway: gatewayPda,
+      gatewaySigners: this.getGatewaySignersPda(gatewayPda).address,
+      successorGateway: successorAuthority
+        ? this.getGatewayPda(successorAuthority).address
+        : null,
+      successorAuthority: successorAuthority || null,
       config: configPda,
     };
 
     return await this.program.methods
       .deletePaymentGateway()
       .accountsStrict(accounts)
+      .remainingAccounts(remainingAccounts)
+      .instruction();
+  }
+
+  async migratePolicyGateway(
+    tokenMint: PublicKey,
+    policyId: number,
+    newGatewayAuthority: PublicKey
+  ): Promise<TransactionInstruction> {
+    const owner = this.provider.publicKey;
+    const { address: userPaymentPda } = this.getUserPaymentPda(
+      owner,
+      tokenMint
+    );
+    const { address: paymentPolicyPda } = this.getPaymentPolicyPda(
+      userPaymentPda,
+      policyId
+    );
+    const { address: configPda } = getConfigPda(this.programId);
+    const paymentPolicy = await this.program.account.paymentPolicy.fetch(
+      paymentPolicyPda
+    );
+
+    const accounts = {
+      owner: owner,
+      userPayment: userPaymentPda,
+      tokenMint: tokenMint,
+      paymentPolicy: paymentPolicyPda,
+      oldGateway: paymentPolicy.gateway,
+      newGateway: this.getGatewayPda(newGatewayAuthority).address,
+      config: configPda,
+    };
+
+    return await this.program.methods
+      .migratePolicyGateway(policyId)
+      .accountsStrict(accounts)
+      .instruction();
+  }
+
+  async migrateGatewayPolicies(
+    oldGatewayAuthority: PublicKey,
+    newGatewayAuthority: PublicKey,
+    policies: PublicKey[]
+  ): Promise<TransactionInstruction> {
+    const { address: configPda } = getConfigPda(this.programId);
+
+    const accounts = {
+      oldAuthority: oldGatewayAuthority,
+      newAuthority: newGatewayAuthority,
+      oldGateway: this.getGatewayPda(oldGatewayAuthority).address,
+      newGateway: this.getGatewayPda(newGatewayAuthority).address,
+      config: configPda,
+    };
+
+    return await this.program.methods
+      .migrateGatewayPolicies()
+      .accountsStrict(accounts)
+      .remainingAccounts(
+        policies.map((policy) => ({
+          pubkey: policy,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .instruction();
+  }
+
+  /**
+   * Count policies created before gateways counted their policies. Pass
+   * `complete` with the last batch once every policy of the gateway is counted.
+   */
+  async backfillGatewayPolicyCount(
+    gatewayAuthority: PublicKey,
+    policies: PublicKey[],
+    complete: boolean
+  ): Promise<TransactionInstruction> {
+    const gatewayApprover = this.provider.publicKey;
+    const { address: configPda } = getConfigPda(this.programId);
+
+    const accounts = {
+      gatewayApprover: gatewayApprover,
+      gateway: this.getGatewayPda(gatewayAuthority).address,
+      config: configPda,
+    };
+
+    return await this.program.methods
+      .backfillGatewayPolicyCount(complete)
+      .accountsStrict(accounts)
+      .remainingAccounts(
+        policies.map((policy) => ({
+          pubkey: policy,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
       .instruction();
   }
 
@@ -731,6 +2608,12 @@ export class Tributary {
     );
   }
 
+  async getGatewaySigners(gateway: PublicKey): Promise<GatewaySigners | null> {
+    return await this.program.account.gatewaySigners.fetchNullable(
+      this.getGatewaySignersPda(gateway).address
+    );
+  }
+
   async getPaymentPolicy(
     policyAddress: PublicKey
   ): Promise<PaymentPolicy | null> {
@@ -738,6 +2621,12 @@ export class Tributary {
       policyAddress
     );
   }
+
+  async getMerchant(merchantAuthority: PublicKey): Promise<Merchant | null> {
+    return await this.program.account.merchant.fetchNullable(
+      this.getMerchantPda(merchantAuthority).address
+    );
+  }
 }
 
 // legacy name
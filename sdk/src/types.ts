@@ -21,9 +21,18 @@ export type ProgramConfig = IdlAccounts<RecurringPayments>["programConfig"];
 export type PaymentGateway = IdlAccounts<RecurringPayments>["paymentGateway"];
 export type UserPayment = IdlAccounts<RecurringPayments>["userPayment"];
 export type PaymentPolicy = IdlAccounts<RecurringPayments>["paymentPolicy"];
+export type GatewaySigners = IdlAccounts<RecurringPayments>["gatewaySigners"];
+export type PaymentEscrow = IdlAccounts<RecurringPayments>["paymentEscrow"];
+export type Merchant = IdlAccounts<RecurringPayments>["merchant"];
 
 // IDL-derived types
 export type PolicyType = IdlTypes<RecurringPayments>["policyType"];
 export type PaymentFrequency = IdlTypes<RecurringPayments>["paymentFrequency"];
 export type PaymentStatus = IdlTypes<RecurringPayments>["paymentStatus"];
 export type PaymentRecord = IdlTypes<RecurringPayments>["paymentRecord"];
+export type GatewaySignerRole = IdlTypes<RecurringPayments>["gatewaySignerRole"];
+export type FundingMode = IdlTypes<RecurringPayments>["fundingMode"];
+export type ConfigRole = IdlTypes<RecurringPayments>["configRole"];
+export type ConfigChange = IdlTypes<RecurringPayments>["configChange"];
+export type PendingConfigChange =
+  IdlAccounts<RecurringPayments>["pendingConfigChange"];
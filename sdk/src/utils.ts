@@ -1,5 +1,9 @@
 import BN from "bn.js";
-import { PaymentFrequency, PaymentFrequencyString } from "./types";
+import {
+  PaymentFrequency,
+  PaymentFrequencyString,
+  PolicyType,
+} from "./types";
 
 /**
  * Encodes a string memo into a fixed-size number array (Uint8Array).
@@ -50,3 +54,16 @@ export function getPaymentFrequency(
       return { daily: {} };
   }
 }
+
+/**
+ * When the next payment of a policy falls due, as a unix timestamp.
+ * Streams pay continuously and have no due date.
+ */
+export function getNextPaymentDue(policyType: PolicyType): number | null {
+  const scheduled =
+    policyType.subscription ||
+    policyType.metered ||
+    policyType.variable ||
+    policyType.fiatSubscription;
+  return scheduled ? scheduled.nextPaymentDue.toNumber() : null;
+}
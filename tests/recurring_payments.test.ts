@@ -14,6 +14,9 @@ import {
   createAssociatedTokenAccount,
   mintTo,
   approve,
+  createAccount,
+  getAccount,
+  NATIVE_MINT,
 } from "@solana/spl-token";
 import { ComputeBudgetProgram } from "@solana/web3.js";
 import { RecurringPayments } from "../target/types/recurring_payments";
@@ -64,6 +67,96 @@ describe("Recurring Payments", () => {
     });
   }
 
+  async function createGateway(authority: Keypair): Promise<PublicKey> {
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const ix = await sdk.createPaymentGateway(
+      authority.publicKey,
+      250,
+      feeRecipient.publicKey,
+      "gateway",
+      "https://example.com"
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+      admin,
+    ]);
+    return sdk.getGatewayPda(authority.publicKey).address;
+  }
+
+  interface Payer {
+    keypair: Keypair;
+    tokenAccount: PublicKey;
+    userPayment: PublicKey;
+  }
+
+  // A funded payer with an approved delegate, so tests don't share policy limits
+  async function createPayer(): Promise<Payer> {
+    const keypair = Keypair.generate();
+    await fund(keypair.publicKey, 2);
+    const tokenAccount = await createAssociatedTokenAccount(
+      connection,
+      keypair,
+      tokenMint,
+      keypair.publicKey
+    );
+    await mintTo(
+      connection,
+      mintAuthority,
+      tokenMint,
+      tokenAccount,
+      mintAuthority,
+      1000000n
+    );
+    await approve(
+      connection,
+      keypair,
+      tokenAccount,
+      paymentsDelegate,
+      keypair,
+      1000000
+    );
+
+    await sdk.updateWallet(new anchor.Wallet(keypair));
+    const ix = await sdk.createUserPayment(tokenMint);
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+      keypair,
+    ]);
+    const userPayment = sdk.getUserPaymentPda(
+      keypair.publicKey,
+      tokenMint
+    ).address;
+    return { keypair, tokenAccount, userPayment };
+  }
+
+  async function createPolicy(
+    gateway: PublicKey,
+    amount: number = 10000,
+    startTime: anchor.BN | null = null,
+    payer?: Payer,
+    holdSeconds: number = 0
+  ): Promise<{ policy: PublicKey; payer: Payer }> {
+    payer = payer || (await createPayer());
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const userPayment = await sdk.getUserPayment(payer.userPayment);
+    const policyId = sdk.getNextPolicyId(userPayment);
+    const ix = await sdk.createPaymentPolicy(
+      tokenMint,
+      recipient.publicKey,
+      gateway,
+      new anchor.BN(amount),
+      true,
+      null,
+      { daily: {} },
+      new Array(64).fill(0),
+      startTime,
+      holdSeconds
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+      payer.keypair,
+    ]);
+    const policy = sdk.getPaymentPolicyPda(payer.userPayment, policyId).address;
+    return { policy, payer };
+  }
+
   beforeAll(async () => {
     // Create Solana Kite connection
     connection = provider.connection;
@@ -376,6 +469,14 @@ describe("Recurring Payments", () => {
     const initialRecipientBalance = await connection.getTokenAccountBalance(
       recipientTokenAccount
     );
+    const gatewayFeeAccount = getAssociatedTokenAddressSync(
+      tokenMint,
+      feeRecipient.publicKey
+    );
+    const protocolFeeAccount = getAssociatedTokenAddressSync(
+      tokenMint,
+      admin.publicKey
+    );
 
     // Update SDK to use gateway authority wallet
     await sdk.updateWallet(new anchor.Wallet(gatewayAuthority));
@@ -395,9 +496,26 @@ describe("Recurring Payments", () => {
       initialRecipientBalance.value.uiAmount || 0
     );
 
+    // 2.5% gateway fee and 1% protocol fee are taken out of the payment
+    expect(
+      Number(finalRecipientBalance.value.amount) -
+        Number(initialRecipientBalance.value.amount)
+    ).toBe(9650);
+    const gatewayFeeBalance = await connection.getTokenAccountBalance(
+      gatewayFeeAccount
+    );
+    expect(gatewayFeeBalance.value.amount).toBe("250");
+    const protocolFeeBalance = await connection.getTokenAccountBalance(
+      protocolFeeAccount
+    );
+    expect(protocolFeeBalance.value.amount).toBe("100");
+
     // Verify policy was updated
     const updatedPolicy = await sdk.getPaymentPolicy(paymentPolicyPDA);
     expect(updatedPolicy!.paymentCount).toBe(1);
+    expect(updatedPolicy!.feesSnapshotted).toBe(true);
+    expect(updatedPolicy!.gatewayFeeBps).toBe(250);
+    expect(updatedPolicy!.protocolFeeBps).toBe(100);
     expect(updatedPolicy!.totalPaid.toNumber()).toBe(10000); // 0.01 token
     expect(
       updatedPolicy!.policyType.subscription.nextPaymentDue.toNumber()
@@ -910,4 +1028,1583 @@ describe("Recurring Payments", () => {
     expect(updatedGateway!.feeRecipient).toEqual(newFeeRecipient.publicKey);
     expect(updatedGateway!.authority).toEqual(gatewayAuthority.publicKey); // authority should remain unchanged
   });
+
+  test("Delete gateway hands its policies to a consenting successor", async () => {
+    const oldAuthority = Keypair.generate();
+    const successorAuthority = Keypair.generate();
+    await fund(successorAuthority.publicKey, 1);
+    const oldGateway = await createGateway(oldAuthority);
+    const successorGateway = await createGateway(successorAuthority);
+    const { policy } = await createPolicy(oldGateway);
+
+    expect((await sdk.getPaymentGateway(oldGateway))!.activePoliciesCount).toBe(
+      1
+    );
+    expect((await sdk.getPaymentPolicy(policy))!.countedByGateway).toBe(true);
+
+    await sdk.updateWallet(new anchor.Wallet(admin));
+
+    // Live policies block deletion without a successor
+    try {
+      const ix = await sdk.deletePaymentGateway(oldAuthority.publicKey);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        admin,
+      ]);
+      assert(false, "Expected deletion with active policies to fail");
+    } catch (error: any) {
+      expect(error.message).toContain("GatewayHasActivePolicies");
+    }
+
+    const ix = await sdk.deletePaymentGateway(
+      oldAuthority.publicKey,
+      successorAuthority.publicKey
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+      admin,
+      successorAuthority,
+    ]);
+
+    expect(await sdk.getPaymentGateway(oldGateway)).toBeNull();
+    expect((await sdk.getPaymentPolicy(policy))!.gateway).toEqual(
+      successorGateway
+    );
+    expect(
+      (await sdk.getPaymentGateway(successorGateway))!.activePoliciesCount
+    ).toBe(1);
+  });
+
+  test("Slashing a bond below the required amount deactivates the gateway", async () => {
+    const operator = Keypair.generate();
+    await fund(operator.publicKey, 2);
+    const operatorTokenAccount = await createAssociatedTokenAccount(
+      connection,
+      operator,
+      tokenMint,
+      operator.publicKey
+    );
+    await mintTo(
+      connection,
+      mintAuthority,
+      tokenMint,
+      operatorTokenAccount,
+      mintAuthority,
+      5000n
+    );
+
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const bondConfigIx = await sdk.setGatewayBondConfig(
+      tokenMint,
+      new anchor.BN(1000),
+      new anchor.BN(0)
+    );
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(bondConfigIx),
+      [admin]
+    );
+
+    await sdk.updateWallet(new anchor.Wallet(operator));
+    const registerIx = await sdk.registerGateway(
+      100,
+      operator.publicKey,
+      "bonded gateway",
+      "https://example.com"
+    );
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(registerIx),
+      [operator]
+    );
+    const { address: operatorGateway } = sdk.getGatewayPda(operator.publicKey);
+    expect((await sdk.getPaymentGateway(operatorGateway))!.isActive).toBe(
+      false
+    );
+
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const approveIx = await sdk.approveGateway(operator.publicKey);
+    await sendAndConfirmTransaction(connection, new Transaction().add(approveIx), [
+      admin,
+    ]);
+    expect((await sdk.getPaymentGateway(operatorGateway))!.isActive).toBe(true);
+
+    const slashIx = await sdk.slashGatewayBond(
+      operator.publicKey,
+      new anchor.BN(400)
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(slashIx), [
+      admin,
+    ]);
+    let gateway = await sdk.getPaymentGateway(operatorGateway);
+    expect(gateway!.bondAmount.toNumber()).toBe(600);
+    expect(gateway!.isActive).toBe(false);
+
+    // Approval waits for the bond to be topped up again
+    try {
+      const ix = await sdk.approveGateway(operator.publicKey);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        admin,
+      ]);
+      assert(false, "Expected approval of an undercollateralized gateway to fail");
+    } catch (error: any) {
+      expect(error.message).toContain("InsufficientBond");
+    }
+
+    await sdk.updateWallet(new anchor.Wallet(operator));
+    const topUpIx = await sdk.topUpGatewayBond(new anchor.BN(400));
+    await sendAndConfirmTransaction(connection, new Transaction().add(topUpIx), [
+      operator,
+    ]);
+
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const reapproveIx = await sdk.approveGateway(operator.publicKey);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(reapproveIx),
+      [admin]
+    );
+    gateway = await sdk.getPaymentGateway(operatorGateway);
+    expect(gateway!.bondAmount.toNumber()).toBe(1000);
+    expect(gateway!.isActive).toBe(true);
+
+    // The bond mint is fixed while bonds are posted
+    const otherMint = await createMint(
+      connection,
+      admin,
+      mintAuthority.publicKey,
+      null,
+      6
+    );
+    try {
+      const ix = await sdk.setGatewayBondConfig(
+        otherMint,
+        new anchor.BN(1000),
+        new anchor.BN(0)
+      );
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        admin,
+      ]);
+      assert(false, "Expected the bond mint not to change while bonds are posted");
+    } catch (error: any) {
+      expect(error.message).toContain("GatewayBondsOutstanding");
+    }
+  });
+
+  test("Gateway signer roles control execution and refunds", async () => {
+    const authority = Keypair.generate();
+    const executor = Keypair.generate();
+    const operator = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    await fund(executor.publicKey, 1);
+    await fund(operator.publicKey, 1);
+    const gateway = await createGateway(authority);
+    const { policy, payer } = await createPolicy(gateway);
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const signersTx = new Transaction().add(
+      await sdk.createGatewaySigners(),
+      await sdk.addGatewaySigner(
+        executor.publicKey,
+        { executor: {} },
+        new anchor.BN(0)
+      ),
+      await sdk.addGatewaySigner(
+        operator.publicKey,
+        { operator: {} },
+        new anchor.BN(0)
+      )
+    );
+    await sendAndConfirmTransaction(connection, signersTx, [authority]);
+
+    await sdk.updateWallet(new anchor.Wallet(executor));
+    const executeIxs = await sdk.executePayment(policy);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(...executeIxs),
+      [executor]
+    );
+    expect((await sdk.getPaymentPolicy(policy))!.paymentCount).toBe(1);
+
+    // Execute-only keys may not issue refunds
+    try {
+      const ix = await sdk.refundPayment(policy, 1, new anchor.BN(50));
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        executor,
+      ]);
+      assert(false, "Expected refund by an executor key to fail");
+    } catch (error: any) {
+      expect(error.message).toContain("Unauthorized");
+    }
+
+    const operatorTokenAccount = await createAssociatedTokenAccount(
+      connection,
+      operator,
+      tokenMint,
+      operator.publicKey
+    );
+    await mintTo(
+      connection,
+      mintAuthority,
+      tokenMint,
+      operatorTokenAccount,
+      mintAuthority,
+      50n
+    );
+    const userBalanceBefore = await connection.getTokenAccountBalance(
+      payer.tokenAccount
+    );
+
+    await sdk.updateWallet(new anchor.Wallet(operator));
+    const refundIx = await sdk.refundPayment(policy, 1, new anchor.BN(50));
+    await sendAndConfirmTransaction(connection, new Transaction().add(refundIx), [
+      operator,
+    ]);
+
+    const userBalanceAfter = await connection.getTokenAccountBalance(
+      payer.tokenAccount
+    );
+    expect(
+      Number(userBalanceAfter.value.amount) -
+        Number(userBalanceBefore.value.amount)
+    ).toBe(50);
+    expect((await sdk.getPaymentPolicy(policy))!.totalRefunded.toNumber()).toBe(
+      50
+    );
+  });
+
+  test("Deleting a gateway closes its signer set", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const createSignersIx = await sdk.createGatewaySigners();
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(createSignersIx),
+      [authority]
+    );
+    expect(await sdk.getGatewaySigners(gateway)).not.toBeNull();
+
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const deleteIx = await sdk.deletePaymentGateway(authority.publicKey);
+    await sendAndConfirmTransaction(connection, new Transaction().add(deleteIx), [
+      admin,
+    ]);
+
+    expect(await sdk.getPaymentGateway(gateway)).toBeNull();
+    expect(await sdk.getGatewaySigners(gateway)).toBeNull();
+
+    // A gateway created again at the same address starts without signers
+    await createGateway(authority);
+    expect(await sdk.getGatewaySigners(gateway)).toBeNull();
+  });
+
+  test("Keepers execute overdue payments for a share of the gateway fee", async () => {
+    const authority = Keypair.generate();
+    const keeper = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    await fund(keeper.publicKey, 1);
+    const gateway = await createGateway(authority);
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const settingsIx = await sdk.setGatewayKeeperSettings(
+      new anchor.BN(3600),
+      5000
+    );
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(settingsIx),
+      [authority]
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    const { policy: recentPolicy } = await createPolicy(
+      gateway,
+      10000,
+      new anchor.BN(now - 60)
+    );
+    const { policy: overduePolicy } = await createPolicy(
+      gateway,
+      10000,
+      new anchor.BN(now - 7200)
+    );
+
+    await sdk.updateWallet(new anchor.Wallet(keeper));
+
+    // Within the grace period only the gateway may execute
+    try {
+      const ixs = await sdk.executePayment(recentPolicy);
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(...ixs),
+        [keeper]
+      );
+      assert(false, "Expected keeper execution within the grace period to fail");
+    } catch (error: any) {
+      expect(error.message).toContain("PaymentNotOverdue");
+    }
+
+    const ixs = await sdk.executePayment(overduePolicy);
+    await sendAndConfirmTransaction(connection, new Transaction().add(...ixs), [
+      keeper,
+    ]);
+
+    // Half of the 2.5% gateway fee goes to the keeper
+    const keeperBalance = await connection.getTokenAccountBalance(
+      getAssociatedTokenAddressSync(tokenMint, keeper.publicKey)
+    );
+    expect(keeperBalance.value.amount).toBe("125");
+    expect((await sdk.getPaymentPolicy(overduePolicy))!.paymentCount).toBe(1);
+  });
+
+  test("Each payment can be refunded at most once in full", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const { policy } = await createPolicy(gateway);
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const executeIxs = await sdk.executePayment(policy);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(...executeIxs),
+      [authority]
+    );
+
+    await sdk.updateWallet(new anchor.Wallet(recipient));
+    const refundIx = await sdk.refundPayment(policy, 1, new anchor.BN(6000));
+    await sendAndConfirmTransaction(connection, new Transaction().add(refundIx), [
+      recipient,
+    ]);
+
+    // The same record cannot be refunded beyond what it charged
+    try {
+      const ix = await sdk.refundPayment(policy, 1, new anchor.BN(6000));
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        recipient,
+      ]);
+      assert(false, "Expected a second full refund of the record to fail");
+    } catch (error: any) {
+      expect(error.message).toContain("RefundExceedsPaid");
+    }
+
+    const remainderIx = await sdk.refundPayment(policy, 1, new anchor.BN(4000));
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(remainderIx),
+      [recipient]
+    );
+
+    const updatedPolicy = await sdk.getPaymentPolicy(policy);
+    expect(updatedPolicy!.totalRefunded.toNumber()).toBe(10000);
+    expect(updatedPolicy!.recentPayments[0].recordId).toBe(1);
+    expect(updatedPolicy!.recentPayments[0].refunded.toNumber()).toBe(10000);
+  });
+
+  test("Disputed escrow can be returned to the payer", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const { policy, payer } = await createPolicy(
+      gateway,
+      10000,
+      null,
+      undefined,
+      3600
+    );
+
+    // The recipient can extend the hold period but never shorten it
+    await sdk.updateWallet(new anchor.Wallet(recipient));
+    try {
+      const ix = await sdk.setPolicyHoldPeriod(policy, 60);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        recipient,
+      ]);
+      assert(false, "Expected shortening the hold period to fail");
+    } catch (error: any) {
+      expect(error.message).toContain("InvalidHoldPeriod");
+    }
+    const raiseIx = await sdk.setPolicyHoldPeriod(policy, 7200);
+    await sendAndConfirmTransaction(connection, new Transaction().add(raiseIx), [
+      recipient,
+    ]);
+    expect((await sdk.getPaymentPolicy(policy))!.holdSeconds).toBe(7200);
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const executeIxs = await sdk.executePayment(policy);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(...executeIxs),
+      [authority]
+    );
+
+    // The recipient's share waits in escrow until the hold period ends
+    const escrowVault = sdk.getEscrowVaultPda(policy, 1).address;
+    const escrowBalance = await connection.getTokenAccountBalance(escrowVault);
+    expect(escrowBalance.value.amount).toBe("9650");
+
+    try {
+      const ix = await sdk.releaseEscrow(policy, 1);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        authority,
+      ]);
+      assert(false, "Expected releasing a held escrow early to fail");
+    } catch (error: any) {
+      expect(error.message).toContain("HoldPeriodActive");
+    }
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const disputeIx = await sdk.openDispute(policy, 1);
+    await sendAndConfirmTransaction(connection, new Transaction().add(disputeIx), [
+      payer.keypair,
+    ]);
+
+    // The policy can not be closed while the dispute is open
+    expect((await sdk.getPaymentPolicy(policy))!.openEscrows).toBe(1);
+    try {
+      const ix = await sdk.deletePaymentPolicy(tokenMint, 1);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        payer.keypair,
+      ]);
+      assert(false, "Expected a policy with a disputed escrow not to close");
+    } catch (error: any) {
+      expect(error.message).toContain("PolicyHasOpenEscrows");
+    }
+
+    const payerBefore = await connection.getTokenAccountBalance(
+      payer.tokenAccount
+    );
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const resolveIx = await sdk.resolveDispute(policy, 1, false);
+    await sendAndConfirmTransaction(connection, new Transaction().add(resolveIx), [
+      authority,
+    ]);
+
+    const payerAfter = await connection.getTokenAccountBalance(
+      payer.tokenAccount
+    );
+    expect(
+      Number(payerAfter.value.amount) - Number(payerBefore.value.amount)
+    ).toBe(9650);
+    expect(
+      await connection.getAccountInfo(sdk.getEscrowPda(policy, 1).address)
+    ).toBeNull();
+    expect((await sdk.getPaymentPolicy(policy))!.totalRefunded.toNumber()).toBe(
+      9650
+    );
+    expect((await sdk.getPaymentPolicy(policy))!.openEscrows).toBe(0);
+  });
+
+  test("Prepaid vault pays policies and can be closed again", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const payer = await createPayer();
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const vaultIxs = [
+      await sdk.createUserPaymentVault(tokenMint),
+      await sdk.depositToVault(tokenMint, new anchor.BN(50000)),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...vaultIxs), [
+      payer.keypair,
+    ]);
+    const { policy } = await createPolicy(gateway, 10000, null, payer);
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const executeIxs = await sdk.executePayment(policy);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(...executeIxs),
+      [authority]
+    );
+
+    const vault = sdk.getUserVaultPda(payer.userPayment).address;
+    const vaultBalance = await connection.getTokenAccountBalance(vault);
+    expect(vaultBalance.value.amount).toBe("40000");
+
+    // Closing the vault returns what is left and goes back to delegation
+    const payerBefore = await connection.getTokenAccountBalance(
+      payer.tokenAccount
+    );
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const closeIx = await sdk.closeUserPaymentVault(tokenMint);
+    await sendAndConfirmTransaction(connection, new Transaction().add(closeIx), [
+      payer.keypair,
+    ]);
+
+    const payerAfter = await connection.getTokenAccountBalance(
+      payer.tokenAccount
+    );
+    expect(
+      Number(payerAfter.value.amount) - Number(payerBefore.value.amount)
+    ).toBe(40000);
+    expect(await connection.getAccountInfo(vault)).toBeNull();
+    const userPayment = await sdk.getUserPayment(payer.userPayment);
+    expect(userPayment!.fundingMode).toEqual({ delegate: {} });
+    expect(userPayment!.vault.equals(PublicKey.default)).toBe(true);
+  });
+
+  test("SOL payments skip fees an empty fee account cannot hold", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const unfundedFeeRecipient = Keypair.generate();
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const gatewayIx = await sdk.createPaymentGateway(
+      authority.publicKey,
+      250,
+      unfundedFeeRecipient.publicKey,
+      "sol gateway",
+      "https://example.com"
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(gatewayIx), [
+      admin,
+    ]);
+    const gateway = sdk.getGatewayPda(authority.publicKey).address;
+
+    const payer = Keypair.generate();
+    await fund(payer.publicKey, 3);
+    await sdk.updateWallet(new anchor.Wallet(payer));
+    const setupIxs = [
+      await sdk.createSolUserPayment(),
+      await sdk.depositSol(new anchor.BN(2 * LAMPORTS_PER_SOL)),
+      await sdk.createPaymentPolicy(
+        NATIVE_MINT,
+        recipient.publicKey,
+        gateway,
+        new anchor.BN(1000000),
+        true,
+        null,
+        { daily: {} },
+        new Array(64).fill(0)
+      ),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...setupIxs), [
+      payer,
+    ]);
+    const userPayment = sdk.getUserPaymentPda(payer.publicKey, NATIVE_MINT).address;
+    const policy = sdk.getPaymentPolicyPda(userPayment, 1).address;
+
+    const recipientBefore = await connection.getBalance(recipient.publicKey);
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const executeIxs = await sdk.executePayment(policy);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(...executeIxs),
+      [authority]
+    );
+
+    // The 2.5% gateway fee is below rent exemption and is not charged
+    const recipientAfter = await connection.getBalance(recipient.publicKey);
+    expect(recipientAfter - recipientBefore).toBe(965000);
+    expect(await connection.getBalance(unfundedFeeRecipient.publicKey)).toBe(0);
+
+    // Refunds go back to the payer's wallet in lamports
+    const payerBefore = await connection.getBalance(payer.publicKey);
+    await sdk.updateWallet(new anchor.Wallet(recipient));
+    const refundIx = await sdk.refundPayment(policy, 1, new anchor.BN(500000));
+    await sendAndConfirmTransaction(connection, new Transaction().add(refundIx), [
+      recipient,
+    ]);
+    expect((await connection.getBalance(payer.publicKey)) - payerBefore).toBe(
+      500000
+    );
+  });
+
+  test("Stopped stream only pays what vested before it stopped", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const payer = await createPayer();
+
+    const now = Math.floor(Date.now() / 1000);
+    const start = new anchor.BN(now - 100);
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const ix = await sdk.createStreamPolicy(
+      tokenMint,
+      recipient.publicKey,
+      gateway,
+      new anchor.BN(10),
+      start,
+      new anchor.BN(now + 100000),
+      new Array(64).fill(0)
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+      payer.keypair,
+    ]);
+    const policy = sdk.getPaymentPolicyPda(payer.userPayment, 1).address;
+
+    const stopIx = await sdk.stopStream(policy);
+    await sendAndConfirmTransaction(connection, new Transaction().add(stopIx), [
+      payer.keypair,
+    ]);
+    const stopped = (await sdk.getPaymentPolicy(policy))!.policyType.stream!;
+    expect(stopped.end.toNumber()).toBeLessThan(now + 100);
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const executeIxs = await sdk.executePayment(policy);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(...executeIxs),
+      [authority]
+    );
+
+    const claimed = (await sdk.getPaymentPolicy(policy))!.policyType.stream!;
+    expect(claimed.withdrawn.toNumber()).toBe(
+      10 * (stopped.end.toNumber() - start.toNumber())
+    );
+  });
+
+  test("Metered policy charges the reported usage up to the cap", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const payer = await createPayer();
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const ix = await sdk.createMeteredPolicy(
+      tokenMint,
+      recipient.publicKey,
+      gateway,
+      new anchor.BN(100),
+      new anchor.BN(50),
+      { daily: {} },
+      new Array(64).fill(0),
+      new anchor.BN(Math.floor(Date.now() / 1000) - 10)
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+      payer.keypair,
+    ]);
+    const policy = sdk.getPaymentPolicyPda(payer.userPayment, 1).address;
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    try {
+      const reportIx = await sdk.submitUsageReport(policy, new anchor.BN(80));
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(reportIx),
+        [authority]
+      );
+      assert(false, "Expected usage above the payer's cap to fail");
+    } catch (error: any) {
+      expect(error.message).toContain("UsageExceedsCap");
+    }
+
+    const reportIx = await sdk.submitUsageReport(policy, new anchor.BN(40));
+    await sendAndConfirmTransaction(connection, new Transaction().add(reportIx), [
+      authority,
+    ]);
+
+    const recipientAta = getAssociatedTokenAddressSync(
+      tokenMint,
+      recipient.publicKey
+    );
+    const recipientBefore = await connection.getTokenAccountBalance(
+      recipientAta
+    );
+    const executeIxs = await sdk.executePayment(policy);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(...executeIxs),
+      [authority]
+    );
+
+    // 40 units at 100 each, less the 3.5% of fees
+    const recipientAfter = await connection.getTokenAccountBalance(
+      recipientAta
+    );
+    expect(
+      Number(recipientAfter.value.amount) -
+        Number(recipientBefore.value.amount)
+    ).toBe(3860);
+    const usageReport = sdk.getUsageReportPda(policy, 1).address;
+    const report = await program.account.usageReport.fetch(usageReport);
+    expect(report.amountCharged.toNumber()).toBe(4000);
+  });
+
+  test("Recipient pulls a variable amount up to the payer's maximum", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const payer = await createPayer();
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const ix = await sdk.createVariablePolicy(
+      tokenMint,
+      recipient.publicKey,
+      gateway,
+      new anchor.BN(5000),
+      { monthly: {} },
+      new Array(64).fill(0)
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+      payer.keypair,
+    ]);
+    const policy = sdk.getPaymentPolicyPda(payer.userPayment, 1).address;
+
+    await sdk.updateWallet(new anchor.Wallet(recipient));
+    try {
+      const ixs = await sdk.executePayment(
+        policy,
+        undefined,
+        undefined,
+        undefined,
+        undefined,
+        new anchor.BN(6000)
+      );
+      await sendAndConfirmTransaction(connection, new Transaction().add(...ixs), [
+        recipient,
+      ]);
+      assert(false, "Expected a pull above the maximum to fail");
+    } catch (error: any) {
+      expect(error.message).toContain("AmountExceedsMax");
+    }
+
+    const invoiceReference = new Array(32).fill(7);
+    const ixs = await sdk.executePayment(
+      policy,
+      undefined,
+      undefined,
+      undefined,
+      undefined,
+      new anchor.BN(4000),
+      invoiceReference
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(...ixs), [
+      recipient,
+    ]);
+
+    const updatedPolicy = await sdk.getPaymentPolicy(policy);
+    expect(updatedPolicy!.totalPaid.toNumber()).toBe(4000);
+    expect(
+      updatedPolicy!.policyType.variable!.recentAmounts[0].toNumber()
+    ).toBe(4000);
+  });
+
+  test("Spending limits span all policies within the window", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const payer = await createPayer();
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const limitIx = await sdk.setSpendingLimit(
+      tokenMint,
+      new anchor.BN(15000),
+      new anchor.BN(24 * 60 * 60)
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(limitIx), [
+      payer.keypair,
+    ]);
+    const { policy: first } = await createPolicy(gateway, 10000, null, payer);
+    const { policy: second } = await createPolicy(gateway, 10000, null, payer);
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const firstIxs = await sdk.executePayment(first);
+    await sendAndConfirmTransaction(connection, new Transaction().add(...firstIxs), [
+      authority,
+    ]);
+    try {
+      const ixs = await sdk.executePayment(second);
+      await sendAndConfirmTransaction(connection, new Transaction().add(...ixs), [
+        authority,
+      ]);
+      assert(false, "Expected the second payment to exceed the spending limit");
+    } catch (error: any) {
+      expect(error.message).toContain("SpendingLimitExceeded");
+    }
+
+    // A recipient cap applies on top of the overall limit
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const raiseIx = await sdk.setSpendingLimit(
+      tokenMint,
+      new anchor.BN(0),
+      new anchor.BN(0)
+    );
+    const capIx = await sdk.createRecipientSpendingCap(
+      tokenMint,
+      recipient.publicKey,
+      new anchor.BN(5000),
+      new anchor.BN(24 * 60 * 60)
+    );
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(raiseIx, capIx),
+      [payer.keypair]
+    );
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    try {
+      const ixs = await sdk.executePayment(second);
+      await sendAndConfirmTransaction(connection, new Transaction().add(...ixs), [
+        authority,
+      ]);
+      assert(false, "Expected the payment to exceed the recipient cap");
+    } catch (error: any) {
+      expect(error.message).toContain("RecipientCapExceeded");
+    }
+  });
+
+  test("Payer pauses all payments and moves them to another token account", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const { policy, payer } = await createPolicy(gateway);
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const pauseIx = await sdk.setUserPaymentActive(tokenMint, false);
+    await sendAndConfirmTransaction(connection, new Transaction().add(pauseIx), [
+      payer.keypair,
+    ]);
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    try {
+      const ixs = await sdk.executePayment(policy);
+      await sendAndConfirmTransaction(connection, new Transaction().add(...ixs), [
+        authority,
+      ]);
+      assert(false, "Expected a paused user payment not to pay");
+    } catch (error: any) {
+      expect(error.message).toContain("ConstraintRaw");
+    }
+
+    // A second, non-associated token account takes over
+    const newTokenAccount = await createAccount(
+      connection,
+      payer.keypair,
+      tokenMint,
+      payer.keypair.publicKey,
+      Keypair.generate()
+    );
+    await mintTo(
+      connection,
+      mintAuthority,
+      tokenMint,
+      newTokenAccount,
+      mintAuthority,
+      50000n
+    );
+    await approve(
+      connection,
+      payer.keypair,
+      newTokenAccount,
+      paymentsDelegate,
+      payer.keypair,
+      50000
+    );
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const moveIxs = [
+      await sdk.updateUserPaymentTokenAccount(tokenMint, newTokenAccount),
+      await sdk.setUserPaymentActive(tokenMint, true),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...moveIxs), [
+      payer.keypair,
+    ]);
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const ixs = await sdk.executePayment(policy);
+    await sendAndConfirmTransaction(connection, new Transaction().add(...ixs), [
+      authority,
+    ]);
+    const balance = await connection.getTokenAccountBalance(newTokenAccount);
+    expect(balance.value.amount).toBe("40000");
+  });
+
+  test("Payer closes the user payment once its policies are gone", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const { payer } = await createPolicy(gateway);
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    try {
+      const ix = await sdk.closeUserPayment(tokenMint);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        payer.keypair,
+      ]);
+      assert(false, "Expected a user payment with policies not to close");
+    } catch (error: any) {
+      expect(error.message).toContain("UserPaymentHasActivePolicies");
+    }
+
+    const closeIxs = [
+      await sdk.deletePaymentPolicy(tokenMint, 1),
+      await sdk.closeUserPayment(tokenMint),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...closeIxs), [
+      payer.keypair,
+    ]);
+
+    expect(await connection.getAccountInfo(payer.userPayment)).toBeNull();
+    const tokenAccount = await getAccount(connection, payer.tokenAccount);
+    expect(tokenAccount.delegate).toBeNull();
+  });
+
+  test("Policy ids are not reused after a policy is deleted", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const { policy: first, payer } = await createPolicy(gateway);
+    const { policy: second } = await createPolicy(gateway, 10000, null, payer);
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const deleteIx = await sdk.deletePaymentPolicy(tokenMint, 1);
+    await sendAndConfirmTransaction(connection, new Transaction().add(deleteIx), [
+      payer.keypair,
+    ]);
+
+    // The count dropped to one, the next id must still skip the live policy 2
+    const { policy: third } = await createPolicy(gateway, 10000, null, payer);
+    expect(await sdk.getPaymentPolicy(first)).toBeNull();
+    expect((await sdk.getPaymentPolicy(second))!.policyId).toBe(2);
+    expect((await sdk.getPaymentPolicy(third))!.policyId).toBe(3);
+    expect((await sdk.getUserPayment(payer.userPayment))!.lastPolicyId).toBe(3);
+  });
+
+  test("Payer and recipient extend the end of a subscription", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const payer = await createPayer();
+    const now = Math.floor(Date.now() / 1000);
+    const endsAt = new anchor.BN(now + 3600);
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const policyId = sdk.getNextPolicyId(await sdk.getUserPayment(payer.userPayment));
+    const createIx = await sdk.createPaymentPolicy(
+      tokenMint,
+      recipient.publicKey,
+      gateway,
+      new anchor.BN(10000),
+      true,
+      null,
+      { daily: {} },
+      new Array(64).fill(0),
+      null,
+      0,
+      endsAt
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(createIx), [
+      payer.keypair,
+    ]);
+    const policy = sdk.getPaymentPolicyPda(payer.userPayment, policyId).address;
+    let policyType = (await sdk.getPaymentPolicy(policy))!.policyType;
+    expect(policyType.subscription!.endsAt.toNumber()).toBe(endsAt.toNumber());
+
+    // Moving the end date forward needs both signatures
+    const extendedEndsAt = endsAt.addn(7 * 86400);
+    const extendIx = await sdk.extendPolicyTerm(
+      policy,
+      recipient.publicKey,
+      extendedEndsAt,
+      null
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(extendIx), [
+      payer.keypair,
+      recipient,
+    ]);
+    policyType = (await sdk.getPaymentPolicy(policy))!.policyType;
+    expect(policyType.subscription!.endsAt.toNumber()).toBe(
+      extendedEndsAt.toNumber()
+    );
+
+    // An end date can not be brought forward
+    try {
+      const ix = await sdk.extendPolicyTerm(policy, recipient.publicKey, endsAt, null);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        payer.keypair,
+        recipient,
+      ]);
+      assert(false, "Expected an earlier end date to be rejected");
+    } catch (error: any) {
+      expect(error.message).toContain("InvalidInterval");
+    }
+
+    // 0 lifts the end date
+    const liftIx = await sdk.extendPolicyTerm(
+      policy,
+      recipient.publicKey,
+      new anchor.BN(0),
+      null
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(liftIx), [
+      payer.keypair,
+      recipient,
+    ]);
+    policyType = (await sdk.getPaymentPolicy(policy))!.policyType;
+    expect(policyType.subscription!.endsAt.toNumber()).toBe(0);
+  });
+
+  test("Payer pauses a policy within the recipient's limit and resumes it", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const { policy, payer } = await createPolicy(gateway);
+    const now = Math.floor(Date.now() / 1000);
+    const dueBeforePause = (await sdk.getPaymentPolicy(policy))!.policyType
+      .subscription!.nextPaymentDue.toNumber();
+
+    await sdk.updateWallet(new anchor.Wallet(recipient));
+    const maxPauseIx = await sdk.setPolicyMaxPause(policy, 2 * 86400);
+    await sendAndConfirmTransaction(connection, new Transaction().add(maxPauseIx), [
+      recipient,
+    ]);
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    try {
+      const ix = await sdk.pausePaymentPolicyUntil(
+        policy,
+        new anchor.BN(now + 10 * 86400)
+      );
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        payer.keypair,
+      ]);
+      assert(false, "Expected a pause beyond the limit to be rejected");
+    } catch (error: any) {
+      expect(error.message).toContain("PauseTooLong");
+    }
+
+    const until = new anchor.BN(now + 86400);
+    const pauseIx = await sdk.pausePaymentPolicyUntil(policy, until);
+    await sendAndConfirmTransaction(connection, new Transaction().add(pauseIx), [
+      payer.keypair,
+    ]);
+    let policyAccount = (await sdk.getPaymentPolicy(policy))!;
+    expect(policyAccount.status).toEqual({ paused: {} });
+    expect(policyAccount.pausedUntil.toNumber()).toBe(until.toNumber());
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    try {
+      const ixs = await sdk.executePayment(policy);
+      await sendAndConfirmTransaction(connection, new Transaction().add(...ixs), [
+        authority,
+      ]);
+      assert(false, "Expected a paused policy not to pay");
+    } catch (error: any) {
+      expect(error.message).toContain("PolicyPaused");
+    }
+
+    // Resuming early skips the period missed while paused
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const resumeIx = await sdk.changePaymentPolicyStatus(tokenMint, 1, {
+      active: {},
+    });
+    await sendAndConfirmTransaction(connection, new Transaction().add(resumeIx), [
+      payer.keypair,
+    ]);
+    policyAccount = (await sdk.getPaymentPolicy(policy))!;
+    expect(policyAccount.status).toEqual({ active: {} });
+    expect(policyAccount.pausedUntil.toNumber()).toBe(0);
+    expect(
+      policyAccount.policyType.subscription!.nextPaymentDue.toNumber()
+    ).toBeGreaterThanOrEqual(dueBeforePause);
+  });
+
+  test("Recipient suspends a policy the payer can not reactivate", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const { policy, payer } = await createPolicy(gateway);
+
+    await sdk.updateWallet(new anchor.Wallet(recipient));
+    const suspendIx = await sdk.recipientSetPolicyStatus(
+      policy,
+      { suspended: {} },
+      7
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(suspendIx), [
+      recipient,
+    ]);
+    expect((await sdk.getPaymentPolicy(policy))!.status).toEqual({
+      suspended: {},
+    });
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    try {
+      const ix = await sdk.changePaymentPolicyStatus(tokenMint, 1, { active: {} });
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        payer.keypair,
+      ]);
+      assert(false, "Expected the payer not to lift a suspension");
+    } catch (error: any) {
+      expect(error.message).toContain("PolicySuspended");
+    }
+
+    // Lifting the suspension hands the policy back to the payer as paused
+    await sdk.updateWallet(new anchor.Wallet(recipient));
+    const liftIx = await sdk.recipientSetPolicyStatus(policy, { paused: {} });
+    await sendAndConfirmTransaction(connection, new Transaction().add(liftIx), [
+      recipient,
+    ]);
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const resumeIx = await sdk.changePaymentPolicyStatus(tokenMint, 1, {
+      active: {},
+    });
+    await sendAndConfirmTransaction(connection, new Transaction().add(resumeIx), [
+      payer.keypair,
+    ]);
+    expect((await sdk.getPaymentPolicy(policy))!.status).toEqual({ active: {} });
+  });
+
+  test("Recipient accepts new policies before they can be charged", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const merchant = Keypair.generate();
+    await fund(merchant.publicKey, 1);
+    await createAssociatedTokenAccount(
+      connection,
+      merchant,
+      tokenMint,
+      merchant.publicKey
+    );
+
+    await sdk.updateWallet(new anchor.Wallet(merchant));
+    const settingsIx = await sdk.createRecipientSettings(true);
+    await sendAndConfirmTransaction(connection, new Transaction().add(settingsIx), [
+      merchant,
+    ]);
+
+    const payer = await createPayer();
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const policies: PublicKey[] = [];
+    for (let policyId = 1; policyId <= 3; policyId++) {
+      const ix = await sdk.createPaymentPolicy(
+        tokenMint,
+        merchant.publicKey,
+        gateway,
+        new anchor.BN(10000),
+        true,
+        null,
+        { daily: {} },
+        new Array(64).fill(0)
+      );
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        payer.keypair,
+      ]);
+      const policy = sdk.getPaymentPolicyPda(payer.userPayment, policyId).address;
+      expect((await sdk.getPaymentPolicy(policy))!.status).toEqual({
+        pendingAcceptance: {},
+      });
+      policies.push(policy);
+    }
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    try {
+      const ixs = await sdk.executePayment(policies[0]);
+      await sendAndConfirmTransaction(connection, new Transaction().add(...ixs), [
+        authority,
+      ]);
+      assert(false, "Expected a pending policy not to pay");
+    } catch (error: any) {
+      expect(error.message).toContain("PolicyPaused");
+    }
+
+    await sdk.updateWallet(new anchor.Wallet(merchant));
+    const acceptIxs = [
+      await sdk.acceptPolicy(policies[0]),
+      await sdk.acceptPolicies(policies.slice(1)),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...acceptIxs), [
+      merchant,
+    ]);
+    for (const policy of policies) {
+      expect((await sdk.getPaymentPolicy(policy))!.status).toEqual({
+        active: {},
+      });
+    }
+
+    // Without acceptance required new policies start right away
+    const optOutIx = await sdk.setRecipientRequireAcceptance(false);
+    await sendAndConfirmTransaction(connection, new Transaction().add(optOutIx), [
+      merchant,
+    ]);
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const ix = await sdk.createPaymentPolicy(
+      tokenMint,
+      merchant.publicKey,
+      gateway,
+      new anchor.BN(10000),
+      true,
+      null,
+      { daily: {} },
+      new Array(64).fill(0)
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+      payer.keypair,
+    ]);
+    const policy = sdk.getPaymentPolicyPda(payer.userPayment, 4).address;
+    expect((await sdk.getPaymentPolicy(policy))!.status).toEqual({ active: {} });
+  });
+
+  test("Gateway verifies a merchant and new policies record it", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const merchant = Keypair.generate();
+    await fund(merchant.publicKey, 1);
+    const settlementAccount = await createAssociatedTokenAccount(
+      connection,
+      merchant,
+      tokenMint,
+      merchant.publicKey
+    );
+    const logoHash = new Array(32).fill(7);
+
+    await sdk.updateWallet(new anchor.Wallet(merchant));
+    const registerIxs = [
+      await sdk.registerMerchant(
+        "Acme",
+        "https://acme.example",
+        logoHash,
+        "support@acme.example"
+      ),
+      await sdk.setMerchantSettlementAccount(tokenMint, settlementAccount),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...registerIxs), [
+      merchant,
+    ]);
+    let profile = (await sdk.getMerchant(merchant.publicKey))!;
+    expect(profile.settlementAccounts.length).toBe(1);
+    expect(profile.verifiedBy).toBeNull();
+
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const verifyIx = await sdk.verifyMerchant(merchant.publicKey);
+    await sendAndConfirmTransaction(connection, new Transaction().add(verifyIx), [
+      authority,
+    ]);
+    profile = (await sdk.getMerchant(merchant.publicKey))!;
+    expect(profile.verifiedBy!.toBase58()).toBe(gateway.toBase58());
+
+    const payer = await createPayer();
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const createIx = await sdk.createPaymentPolicy(
+      tokenMint,
+      merchant.publicKey,
+      gateway,
+      new anchor.BN(10000),
+      true,
+      null,
+      { daily: {} },
+      new Array(64).fill(0)
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(createIx), [
+      payer.keypair,
+    ]);
+    const policy = sdk.getPaymentPolicyPda(payer.userPayment, 1).address;
+    expect(
+      (await sdk.getPaymentPolicy(policy))!.merchantVerifiedBy!.toBase58()
+    ).toBe(gateway.toBase58());
+
+    // Changing the profile clears the verification
+    await sdk.updateWallet(new anchor.Wallet(merchant));
+    const updateIxs = [
+      await sdk.updateMerchant(
+        "Acme Inc",
+        "https://acme.example",
+        logoHash,
+        "support@acme.example"
+      ),
+      await sdk.removeMerchantSettlementAccount(tokenMint),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...updateIxs), [
+      merchant,
+    ]);
+    profile = (await sdk.getMerchant(merchant.publicKey))!;
+    expect(profile.verifiedBy).toBeNull();
+    expect(profile.settlementAccounts.length).toBe(0);
+  });
+
+  test("Compliance suspends the policies of a denylisted recipient", async () => {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey, 2);
+    const gateway = await createGateway(authority);
+    const merchant = Keypair.generate();
+    await fund(merchant.publicKey, 1);
+    await createAssociatedTokenAccount(
+      connection,
+      merchant,
+      tokenMint,
+      merchant.publicKey
+    );
+
+    const payer = await createPayer();
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const createIx = await sdk.createPaymentPolicy(
+      tokenMint,
+      merchant.publicKey,
+      gateway,
+      new anchor.BN(10000),
+      true,
+      null,
+      { daily: {} },
+      new Array(64).fill(0)
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(createIx), [
+      payer.keypair,
+    ]);
+    const policy = sdk.getPaymentPolicyPda(payer.userPayment, 1).address;
+    const secondIx = await sdk.createPaymentPolicy(
+      tokenMint,
+      merchant.publicKey,
+      gateway,
+      new anchor.BN(10000),
+      true,
+      null,
+      { daily: {} },
+      new Array(64).fill(0)
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(secondIx), [
+      payer.keypair,
+    ]);
+    const suspendedPolicy = sdk.getPaymentPolicyPda(payer.userPayment, 2).address;
+    await sdk.updateWallet(new anchor.Wallet(merchant));
+    const recipientSuspendIx = await sdk.recipientSetPolicyStatus(suspendedPolicy, {
+      suspended: {},
+    });
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(recipientSuspendIx),
+      [merchant]
+    );
+
+    // The admin holds the Compliance role until it is assigned
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const suspendIxs = [
+      await sdk.addToDenylist(merchant.publicKey, 3),
+      await sdk.suspendDenylistedPolicies(merchant.publicKey, [
+        policy,
+        suspendedPolicy,
+      ]),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...suspendIxs), [
+      admin,
+    ]);
+    expect((await sdk.getPaymentPolicy(policy))!.status).toEqual({
+      complianceSuspended: {},
+    });
+
+    // Neither the recipient nor the payer can undo it
+    await sdk.updateWallet(new anchor.Wallet(merchant));
+    try {
+      const ix = await sdk.recipientSetPolicyStatus(policy, { paused: {} });
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        merchant,
+      ]);
+      assert(false, "Expected the recipient not to lift a compliance suspension");
+    } catch (error: any) {
+      expect(error.message).toContain("PolicyComplianceSuspended");
+    }
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    try {
+      const ix = await sdk.changePaymentPolicyStatus(tokenMint, 1, { active: {} });
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        payer.keypair,
+      ]);
+      assert(false, "Expected the payer not to lift a compliance suspension");
+    } catch (error: any) {
+      expect(error.message).toContain("PolicyComplianceSuspended");
+    }
+
+    // The recipient must be cleared first
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    try {
+      const ix = await sdk.liftComplianceSuspension([policy]);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        admin,
+      ]);
+      assert(false, "Expected the suspension not to lift while denylisted");
+    } catch (error: any) {
+      expect(error.message).toContain("RecipientDenylisted");
+    }
+
+    const removeIx = await sdk.removeFromDenylist(merchant.publicKey);
+    await sendAndConfirmTransaction(connection, new Transaction().add(removeIx), [
+      admin,
+    ]);
+    const liftIx = await sdk.liftComplianceSuspension([policy, suspendedPolicy]);
+    await sendAndConfirmTransaction(connection, new Transaction().add(liftIx), [
+      admin,
+    ]);
+    expect((await sdk.getPaymentPolicy(policy))!.status).toEqual({ paused: {} });
+    // The recipient's suspension is kept, so the payer can not resume it
+    expect((await sdk.getPaymentPolicy(suspendedPolicy))!.status).toEqual({
+      suspended: {},
+    });
+
+    await sdk.updateWallet(new anchor.Wallet(payer.keypair));
+    const resumeIx = await sdk.changePaymentPolicyStatus(tokenMint, 1, {
+      active: {},
+    });
+    await sendAndConfirmTransaction(connection, new Transaction().add(resumeIx), [
+      payer.keypair,
+    ]);
+    await sdk.updateWallet(new anchor.Wallet(authority));
+    const ixs = await sdk.executePayment(policy);
+    await sendAndConfirmTransaction(connection, new Transaction().add(...ixs), [
+      authority,
+    ]);
+    expect((await sdk.getPaymentPolicy(policy))!.paymentCount).toBe(1);
+  });
+
+  test("Config roles are held apart from the admin", async () => {
+    const approver = Keypair.generate();
+    await fund(approver.publicKey, 2);
+    const pauser = Keypair.generate();
+    await fund(pauser.publicKey, 1);
+
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const assignIxs = [
+      await sdk.setConfigRole({ gatewayApprover: {} }, approver.publicKey),
+      await sdk.setConfigRole({ pauser: {} }, pauser.publicKey),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...assignIxs), [
+      admin,
+    ]);
+    const config = await sdk.getProgramConfig();
+    expect(config!.gatewayApprover.toBase58()).toBe(approver.publicKey.toBase58());
+    expect(config!.pauser.toBase58()).toBe(pauser.publicKey.toBase58());
+
+    // Only the approver creates gateways now
+    const authority = Keypair.generate();
+    try {
+      await createGateway(authority);
+      assert(false, "Expected the admin not to create gateways");
+    } catch (error: any) {
+      expect(error.message).toContain("Unauthorized");
+    }
+    await sdk.updateWallet(new anchor.Wallet(approver));
+    const gatewayIx = await sdk.createPaymentGateway(
+      authority.publicKey,
+      250,
+      feeRecipient.publicKey,
+      "gateway",
+      "https://example.com"
+    );
+    await sendAndConfirmTransaction(connection, new Transaction().add(gatewayIx), [
+      approver,
+    ]);
+    expect(
+      await sdk.getPaymentGateway(sdk.getGatewayPda(authority.publicKey).address)
+    ).not.toBeNull();
+
+    // The pauser can pause but only the admin lifts the pause
+    await sdk.updateWallet(new anchor.Wallet(pauser));
+    const pauseIx = await sdk.setEmergencyPause(true);
+    await sendAndConfirmTransaction(connection, new Transaction().add(pauseIx), [
+      pauser,
+    ]);
+    try {
+      const ix = await sdk.setEmergencyPause(false);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        pauser,
+      ]);
+      assert(false, "Expected the pauser not to lift the pause");
+    } catch (error: any) {
+      expect(error.message).toContain("Unauthorized");
+    }
+
+    // Hand the roles back to the admin for the remaining tests
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const resetIxs = [
+      await sdk.setEmergencyPause(false),
+      await sdk.setConfigRole({ gatewayApprover: {} }, PublicKey.default),
+      await sdk.setConfigRole({ pauser: {} }, PublicKey.default),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...resetIxs), [
+      admin,
+    ]);
+    expect((await sdk.getProgramConfig())!.emergencyPause).toBe(false);
+  });
+
+  test("Config changes wait out the timelock", async () => {
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const config = (await sdk.getProgramConfig())!;
+
+    try {
+      const ix = await sdk.queueConfigChange({
+        timelockSeconds: { 0: new anchor.BN(0) },
+      });
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        admin,
+      ]);
+      assert(false, "Expected a zero timelock to be rejected");
+    } catch (error: any) {
+      expect(error.message).toContain("InvalidInterval");
+    }
+
+    const change = { maxPoliciesPerUser: { 0: config.maxPoliciesPerUser + 1 } };
+    const kind = sdk.getConfigChangeKind(change);
+    const queueIx = await sdk.queueConfigChange(change);
+    await sendAndConfirmTransaction(connection, new Transaction().add(queueIx), [
+      admin,
+    ]);
+    const pending = (await sdk.getPendingConfigChange(kind))!;
+    const timelock = config.timelockSeconds.isZero()
+      ? 2 * 24 * 60 * 60
+      : config.timelockSeconds.toNumber();
+    expect(
+      pending.executableAt.toNumber() - pending.queuedAt.toNumber()
+    ).toBe(timelock);
+
+    try {
+      const ix = await sdk.executeConfigChange(kind);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        admin,
+      ]);
+      assert(false, "Expected the change to wait out the timelock");
+    } catch (error: any) {
+      expect(error.message).toContain("TimelockNotElapsed");
+    }
+
+    // Only the role can cancel a live change
+    const stranger = Keypair.generate();
+    await fund(stranger.publicKey, 1);
+    await sdk.updateWallet(new anchor.Wallet(stranger));
+    try {
+      const ix = await sdk.cancelConfigChange(kind);
+      await sendAndConfirmTransaction(connection, new Transaction().add(ix), [
+        stranger,
+      ]);
+      assert(false, "Expected a stranger not to cancel a live change");
+    } catch (error: any) {
+      expect(error.message).toContain("Unauthorized");
+    }
+
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const cancelIx = await sdk.cancelConfigChange(kind);
+    await sendAndConfirmTransaction(connection, new Transaction().add(cancelIx), [
+      admin,
+    ]);
+    expect(await sdk.getPendingConfigChange(kind)).toBeNull();
+    expect((await sdk.getProgramConfig())!.maxPoliciesPerUser).toBe(
+      config.maxPoliciesPerUser
+    );
+
+    // A change lapses once its queuer loses the role, then anyone can clear it
+    const feeChange = { protocolFeeBps: { 0: config.protocolFeeBps } };
+    const feeKind = sdk.getConfigChangeKind(feeChange);
+    const feeManager = Keypair.generate();
+    const lapseIxs = [
+      await sdk.queueConfigChange(feeChange),
+      await sdk.setConfigRole({ feeManager: {} }, feeManager.publicKey),
+    ];
+    await sendAndConfirmTransaction(connection, new Transaction().add(...lapseIxs), [
+      admin,
+    ]);
+    await sdk.updateWallet(new anchor.Wallet(stranger));
+    const clearIx = await sdk.cancelConfigChange(feeKind);
+    await sendAndConfirmTransaction(connection, new Transaction().add(clearIx), [
+      stranger,
+    ]);
+    expect(await sdk.getPendingConfigChange(feeKind)).toBeNull();
+
+    await sdk.updateWallet(new anchor.Wallet(admin));
+    const resetIx = await sdk.setConfigRole({ feeManager: {} }, PublicKey.default);
+    await sendAndConfirmTransaction(connection, new Transaction().add(resetIx), [
+      admin,
+    ]);
+  });
 });